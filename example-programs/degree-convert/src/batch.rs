@@ -0,0 +1,207 @@
+use std::fmt;
+use std::io::{self, BufRead, Write};
+
+use crate::number::NumberFormat;
+use crate::units::{Quantity, Unit};
+
+// Converts one column of comma separated rows, e.g. a CSV export or values piped into stdin.
+// Every row is written out again with the converted value appended as a new last column.
+// Fields are split on the separator of the locale only, quoted fields with one inside are not supported.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Batch {
+    // 1-based
+    pub column: usize,
+    pub from: Option<&'static Unit>,
+    pub to: Option<&'static Unit>,
+    pub header: bool,
+    // how converted values are written, its locale also picks "," or ";" between fields
+    pub numbers: NumberFormat,
+}
+
+// A row that could not be converted. It is left out of the output and the run goes on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RowError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for RowError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Report {
+    pub converted: usize,
+    pub skipped: Vec<RowError>,
+}
+
+impl Batch {
+    // Only reading `input` and writing `output` can fail the whole run, bad rows end up in the report.
+    pub fn run(&self, input: impl BufRead, output: &mut impl Write) -> io::Result<Report> {
+        let mut report = Report::default();
+        let separator = self.numbers.locale.field_separator();
+
+        for (index, line) in input.lines().enumerate() {
+            let line = line?;
+            let number = index + 1;
+
+            if number == 1 && self.header {
+                let name = match self.to {
+                    Some(to) => to.symbol,
+                    None => "converted",
+                };
+                writeln!(output, "{line}{separator}{name}")?;
+                continue;
+            }
+
+            // blank lines, e.g. at the end of a file, are not worth a complaint
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            match self.convert(&line) {
+                Ok(converted) => {
+                    writeln!(output, "{line}{separator}{converted}")?;
+                    report.converted += 1;
+                }
+                Err(message) => report.skipped.push(RowError {
+                    line: number,
+                    message,
+                }),
+            }
+        }
+
+        Ok(report)
+    }
+
+    fn convert(&self, line: &str) -> Result<String, String> {
+        let separator = self.numbers.locale.field_separator();
+        let field = line.split(separator).nth(self.column - 1).ok_or_else(|| {
+            format!(
+                "has no column {}, only {}",
+                self.column,
+                line.split(separator).count()
+            )
+        })?;
+
+        let quantity = Quantity::parse(field, self.from, self.numbers.locale)
+            .map_err(|err| err.to_string())?;
+
+        // with a fixed target every value is in the same unit, so the header names it once
+        match self.to.or(quantity.counterpart()) {
+            Some(to) => {
+                let converted = quantity.to(to).map_err(|err| err.to_string())?;
+
+                Ok(match self.to {
+                    Some(_) => self.numbers.format(converted.value),
+                    None => converted.format(&self.numbers),
+                })
+            }
+            None => Err(format!("`{}` needs a target unit, use --to", field.trim())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::Locale;
+
+    fn run(batch: &Batch, input: &str) -> (String, Report) {
+        let mut output = Vec::new();
+        let report = batch.run(input.as_bytes(), &mut output).unwrap();
+
+        (String::from_utf8(output).unwrap(), report)
+    }
+
+    #[test]
+    fn column_is_converted_and_appended() {
+        let batch = Batch {
+            column: 2,
+            from: Unit::find("F"),
+            to: Unit::find("C"),
+            header: true,
+            numbers: NumberFormat::default(),
+        };
+
+        let (output, report) = run(&batch, "city,temp\nOslo,32\nCairo,212\n");
+
+        assert_eq!(output, "city,temp,°C\nOslo,32,0\nCairo,212,100\n");
+        assert_eq!(report.converted, 2);
+        assert!(report.skipped.is_empty());
+    }
+
+    #[test]
+    fn bad_rows_are_reported_with_line_numbers() {
+        let batch = Batch {
+            column: 1,
+            from: None,
+            to: None,
+            header: false,
+            numbers: NumberFormat::default(),
+        };
+
+        let (output, report) = run(&batch, "100C\nwarm\n\n-500F\n300\n0K\n5 km\n");
+
+        assert_eq!(output, "100C,212°F\n0K,-273.15°C\n");
+        assert_eq!(report.converted, 2);
+
+        let lines: Vec<usize> = report.skipped.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [2, 4, 5, 7]);
+        assert_eq!(
+            report.skipped[0].to_string(),
+            "line 2: `warm` is not a number"
+        );
+    }
+
+    #[test]
+    fn other_units_convert_too() {
+        let batch = Batch {
+            column: 1,
+            from: Unit::find("mi"),
+            to: Unit::find("km"),
+            header: false,
+            numbers: NumberFormat::default(),
+        };
+
+        let (output, report) = run(&batch, "1\n2 kg\n");
+
+        assert_eq!(output, "1,1.609344\n");
+        assert_eq!(report.skipped[0].line, 2);
+    }
+
+    #[test]
+    fn comma_locale_reads_semicolon_rows() {
+        let batch = Batch {
+            column: 2,
+            from: Unit::find("C"),
+            to: Unit::find("F"),
+            header: false,
+            numbers: NumberFormat {
+                locale: Locale::Comma,
+                ..NumberFormat::default()
+            },
+        };
+
+        let (output, _) = run(&batch, "Ana;36,6\n");
+
+        assert_eq!(output, "Ana;36,6;97,88\n");
+    }
+
+    #[test]
+    fn missing_column_is_reported() {
+        let batch = Batch {
+            column: 3,
+            from: Unit::find("C"),
+            to: Unit::find("K"),
+            header: false,
+            numbers: NumberFormat::default(),
+        };
+
+        let (_, report) = run(&batch, "a,1\n");
+
+        assert_eq!(report.skipped[0].message, "has no column 3, only 2");
+    }
+}
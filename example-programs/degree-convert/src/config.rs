@@ -0,0 +1,422 @@
+use std::path::PathBuf;
+
+use crate::expression::Expression;
+use crate::number::{parse_number, Locale, NumberFormat, Precision};
+use crate::table::{Format, Table};
+use crate::units::{Quantity, Unit, UnitError};
+
+// Decimal places in a table when neither `--decimals` nor `--digits` is given.
+pub const DEFAULT_TABLE_DECIMALS: usize = 2;
+
+// Far past the 17 digits an f64 really has, more only pads the output with zeros.
+pub const MAX_PRECISION: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mode {
+    // no arguments: ask for a temperature and a scale on stdin
+    Interactive,
+    // `degree-convert 98.6F --to C` or `degree-convert "5 km in miles"`
+    Convert(Quantity),
+    // `degree-convert --batch values.csv --column 2 --from F --to C`, stdin when there is no file
+    Batch {
+        input: Option<PathBuf>,
+        // 1-based, like the columns of a spreadsheet
+        column: usize,
+        // unit of values that are written without one, e.g. a plain "98.6"
+        from: Option<&'static Unit>,
+        header: bool,
+    },
+    // `degree-convert --table C --start 0 --end 100 --step 10 --format markdown`
+    Table {
+        table: Table,
+        format: Format,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Config {
+    pub mode: Mode,
+    // `None` converts temperatures to their counterpart scale, see `Quantity::counterpart`
+    pub to: Option<&'static Unit>,
+    // `--decimals`, `--digits`, `--rounding` and `--locale`
+    pub numbers: NumberFormat,
+}
+
+impl Config {
+    // The command line as typed, e.g. `degree-convert 98.6F --to C`.
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+
+        let mut args = args.peekable();
+        let mut to = None;
+        let mut batch = false;
+        let mut input = None;
+        let mut column = 1;
+        let mut from = None;
+        let mut header = false;
+        // only used together with `--table`
+        let mut table = None;
+        let mut start = None;
+        let mut end = None;
+        let mut step = None;
+        let mut format = Format::Text;
+        let mut numbers = NumberFormat::default();
+        // tables get fixed decimals unless a precision is asked for
+        let mut precision = None;
+        // "98.6F", "98.6 F" or "100 F in C", everything that is not a flag
+        let mut words = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--to" => to = Some(unit_of(&arg, args.next())?),
+                "--from" => from = Some(unit_of(&arg, args.next())?),
+                "--batch" => {
+                    batch = true;
+                    // the file is optional, stdin is read without it
+                    if let Some(next) = args.next_if(|next| !next.starts_with("--")) {
+                        input = Some(PathBuf::from(next));
+                    }
+                }
+                "--column" => {
+                    let value = value_of(&arg, args.next())?;
+                    column = match value.parse() {
+                        Ok(column) if column > 0 => column,
+                        _ => {
+                            return Err(format!(
+                                "`{value}` is not a valid column, columns start at 1"
+                            ))
+                        }
+                    };
+                }
+                "--header" => header = true,
+                "--table" => table = Some(unit_of(&arg, args.next())?),
+                "--start" => start = Some(value_of(&arg, args.next())?),
+                "--end" => end = Some(value_of(&arg, args.next())?),
+                "--step" => step = Some(value_of(&arg, args.next())?),
+                "--format" => format = value_of(&arg, args.next())?.parse()?,
+                "--decimals" | "--digits" => {
+                    let value = value_of(&arg, args.next())?;
+                    let count: usize = value
+                        .parse()
+                        .map_err(|_| format!("`{value}` is not a valid value for `{arg}`"))?;
+
+                    if precision.is_some() {
+                        return Err(String::from(
+                            "`--decimals` and `--digits` cannot be used together",
+                        ));
+                    }
+
+                    if count > MAX_PRECISION {
+                        return Err(format!("`{arg}` can be at most {MAX_PRECISION}"));
+                    }
+
+                    precision = Some(match arg.as_str() {
+                        "--decimals" => Precision::Decimals(count),
+                        _ if count == 0 => {
+                            return Err(String::from("`--digits` must be at least 1"))
+                        }
+                        _ => Precision::Significant(count),
+                    });
+                }
+                "--rounding" => numbers.rounding = value_of(&arg, args.next())?.parse()?,
+                "--locale" => numbers.locale = value_of(&arg, args.next())?.parse()?,
+                other if other.starts_with("--") => {
+                    return Err(format!("unknown argument `{other}`"))
+                }
+                _ => words.push(arg),
+            }
+        }
+
+        // numbers are read once `--locale` is known, wherever it was given
+        let start = number_of("--start", start, numbers.locale)?;
+        let end = number_of("--end", end, numbers.locale)?;
+        let step = number_of("--step", step, numbers.locale)?;
+
+        numbers.precision = match (precision, table) {
+            (Some(precision), _) => precision,
+            (None, Some(_)) => Precision::Decimals(DEFAULT_TABLE_DECIMALS),
+            (None, None) => Precision::Shortest,
+        };
+
+        let mode = if let Some(from) = table {
+            let (Some(start), Some(end)) = (start, end) else {
+                return Err(String::from("`--table` needs `--start` and `--end`"));
+            };
+
+            if let Some(to) = to.filter(|to| to.dimension != from.dimension) {
+                return Err(UnitError::Dimension { from, to }.to_string());
+            }
+
+            Mode::Table {
+                table: Table {
+                    from,
+                    to,
+                    start,
+                    end,
+                    // one unit at a time, counting down when the end is below the start
+                    step: step.unwrap_or(if end < start { -1.0 } else { 1.0 }),
+                },
+                format,
+            }
+        } else if batch {
+            if !words.is_empty() {
+                return Err(format!(
+                    "`{}` cannot be converted together with `--batch`",
+                    words.join(" ")
+                ));
+            }
+
+            Mode::Batch {
+                input,
+                column,
+                from,
+                header,
+            }
+        } else if words.is_empty() {
+            // the questions on stdin are only about temperatures
+            if let Some(to) = to.filter(|to| to.scale().is_none()) {
+                return Err(format!("`--to {to}` is not a temperature scale"));
+            }
+
+            Mode::Interactive
+        } else {
+            let input = words.join(" ");
+            let expression = Expression::parse(&input, numbers.locale)
+                .map_err(|err| format!("{err}\n{}", err.pointer(&input)))?;
+
+            // "100 F in C --to K" asks for two different things
+            match (expression.to, to) {
+                (Some(written), Some(flag)) if written != flag => {
+                    return Err(format!(
+                        "`{input}` converts to {written} but `--to` asks for {flag}"
+                    ))
+                }
+                (Some(written), _) => to = Some(written),
+                (None, Some(flag)) if flag.dimension != expression.quantity.unit.dimension => {
+                    let error = UnitError::Dimension {
+                        from: expression.quantity.unit,
+                        to: flag,
+                    };
+                    return Err(error.to_string());
+                }
+                _ => {}
+            }
+
+            Mode::Convert(expression.quantity)
+        };
+
+        Ok(Config { mode, to, numbers })
+    }
+}
+
+fn value_of(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("`{flag}` needs a value"))
+}
+
+fn number_of(flag: &str, value: Option<String>, locale: Locale) -> Result<Option<f64>, String> {
+    value
+        .map(|value| {
+            parse_number(&value, locale)
+                .ok_or_else(|| format!("`{value}` is not a valid value for `{flag}`"))
+        })
+        .transpose()
+}
+
+fn unit_of(flag: &str, value: Option<String>) -> Result<&'static Unit, String> {
+    let value = value_of(flag, value)?;
+
+    Unit::find(&value).ok_or_else(|| UnitError::UnknownUnit(value).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::Rounding;
+
+    fn unit(text: &str) -> &'static Unit {
+        Unit::find(text).unwrap()
+    }
+
+    fn quantity(value: f64, text: &str) -> Quantity {
+        Quantity::new(value, unit(text)).unwrap()
+    }
+
+    fn build(args: &[&str]) -> Result<Config, String> {
+        let args = std::iter::once("degree-convert")
+            .chain(args.iter().copied())
+            .map(String::from);
+
+        Config::build(args)
+    }
+
+    #[test]
+    fn no_arguments_is_interactive() {
+        assert_eq!(build(&[]).unwrap().mode, Mode::Interactive);
+    }
+
+    #[test]
+    fn single_conversion() {
+        let config = build(&["98.6F", "--to", "C"]).unwrap();
+        assert_eq!(config.mode, Mode::Convert(quantity(98.6, "F")));
+        assert_eq!(config.to, Some(unit("C")));
+
+        let config = build(&["-40", "C"]).unwrap();
+        assert_eq!(config.mode, Mode::Convert(quantity(-40.0, "C")));
+        assert_eq!(config.to, None);
+
+        let config = build(&["100 F in C"]).unwrap();
+        assert_eq!(config.mode, Mode::Convert(quantity(100.0, "F")));
+        assert_eq!(config.to, Some(unit("C")));
+
+        let config = build(&["-40°C", "to", "kelvin", "--to", "K"]).unwrap();
+        assert_eq!(config.to, Some(unit("K")));
+
+        let config = build(&["5", "km", "--to", "mi"]).unwrap();
+        assert_eq!(config.mode, Mode::Convert(quantity(5.0, "km")));
+        assert_eq!(config.to, Some(unit("mi")));
+    }
+
+    #[test]
+    fn expression_errors_point_at_token() {
+        let err = build(&["100 F on C"]).unwrap_err();
+        assert!(err.ends_with("100 F on C\n      ^^"), "error was: {err}");
+
+        assert!(build(&["100 F in C", "--to", "K"]).is_err());
+        assert!(build(&["3 m", "--to", "kg"]).is_err());
+        assert!(build(&["--to", "kg"]).is_err());
+    }
+
+    #[test]
+    fn batch_from_file_or_stdin() {
+        assert_eq!(
+            build(&["--batch", "--to", "K"]).unwrap().mode,
+            Mode::Batch {
+                input: None,
+                column: 1,
+                from: None,
+                header: false
+            }
+        );
+        assert_eq!(
+            build(&[
+                "--batch",
+                "temps.csv",
+                "--column",
+                "3",
+                "--from",
+                "F",
+                "--header"
+            ])
+            .unwrap()
+            .mode,
+            Mode::Batch {
+                input: Some(PathBuf::from("temps.csv")),
+                column: 3,
+                from: Some(unit("F")),
+                header: true
+            }
+        );
+    }
+
+    #[test]
+    fn table_mode() {
+        let config = build(&[
+            "--table", "C", "--start", "0", "--end", "100", "--step", "10", "--format", "md",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            config.mode,
+            Mode::Table {
+                table: Table {
+                    from: unit("C"),
+                    to: None,
+                    start: 0.0,
+                    end: 100.0,
+                    step: 10.0
+                },
+                format: Format::Markdown
+            }
+        );
+        assert_eq!(
+            config.numbers.precision,
+            Precision::Decimals(DEFAULT_TABLE_DECIMALS)
+        );
+
+        let config = build(&[
+            "--table",
+            "km",
+            "--start",
+            "10",
+            "--end",
+            "0",
+            "--to",
+            "mi",
+            "--decimals",
+            "4",
+        ])
+        .unwrap();
+
+        let Mode::Table { table, .. } = config.mode else {
+            panic!("expected a table, got {:?}", config.mode);
+        };
+        assert_eq!(table.step, -1.0);
+        assert_eq!(table.to, Some(unit("mi")));
+        assert_eq!(config.numbers.precision, Precision::Decimals(4));
+
+        assert!(build(&["--table", "C", "--start", "0"]).is_err());
+        assert!(build(&["--table", "C", "--start", "0", "--end", "1", "--to", "m"]).is_err());
+        assert!(build(&["--table", "C", "--start", "0", "--end", "1", "--format", "xml"]).is_err());
+    }
+
+    #[test]
+    fn number_format() {
+        let config = build(&["98.6F"]).unwrap();
+        assert_eq!(config.numbers, NumberFormat::default());
+
+        // the locale decides what a comma means, even when it comes after the value
+        let config = build(&["36,6C", "--locale", "de"]).unwrap();
+        assert_eq!(config.mode, Mode::Convert(quantity(36.6, "C")));
+        assert!(build(&["36,6C"]).is_err());
+
+        let config = build(&["1,000 m", "--to", "km", "--locale", "en"]).unwrap();
+        assert_eq!(config.mode, Mode::Convert(quantity(1000.0, "m")));
+
+        let config = build(&[
+            "1 mi",
+            "--to",
+            "km",
+            "--digits",
+            "3",
+            "--rounding",
+            "half-up",
+        ])
+        .unwrap();
+        assert_eq!(config.numbers.precision, Precision::Significant(3));
+        assert_eq!(config.numbers.rounding, Rounding::HalfUp);
+
+        let config = build(&["--batch", "--locale", "de", "--from", "C", "--to", "F"]).unwrap();
+        assert_eq!(config.numbers.locale, Locale::Comma);
+
+        assert!(build(&["1C", "--decimals", "2", "--digits", "3"]).is_err());
+        assert!(build(&["1C", "--digits", "0"]).is_err());
+        assert!(build(&["1C", "--decimals", "100"]).is_ok());
+        assert_eq!(
+            build(&["1C", "--decimals", "18446744073709551615"]),
+            Err(String::from("`--decimals` can be at most 100"))
+        );
+        assert!(build(&["1C", "--digits", "1000000000000"]).is_err());
+        assert!(build(&["1C", "--rounding", "sideways"]).is_err());
+        assert!(build(&["1C", "--locale", "klingon"]).is_err());
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        assert!(build(&["98.6F", "--to"]).is_err());
+        assert!(build(&["98.6F", "--to", "X"]).is_err());
+        assert!(build(&["-500C"]).is_err());
+        assert!(build(&["--batch", "--column", "0"]).is_err());
+        assert!(build(&["12C", "--batch"]).is_err());
+        assert!(build(&["--fast"]).is_err());
+    }
+}
@@ -0,0 +1,600 @@
+use std::fmt;
+
+use crate::number::{parse_number, Locale};
+use crate::units::{Dimension, Quantity, Unit, UnitError};
+
+// Conversion requests written the way people say them:
+//
+//   expression = number unit [connector unit]
+//   unit       = ["degree" | "degrees" | "deg" | "°"] any unit of the unit table
+//   connector  = "in" | "to" | "as" | "into" | "->"
+//
+// e.g. "100 F in C", "-40°C to kelvin", "300K as F", "5 km in miles" or just "98.6 degrees fahrenheit".
+// Unit names can be several words, "3 nautical miles to km" or "2 fluid ounces in ml".
+
+#[derive(Debug, Clone, PartialEq)]
+enum TokenKind {
+    Number(f64),
+    // unit names, symbols and connectors, the parser decides which one it is
+    Word(String),
+    Arrow,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Token {
+    kind: TokenKind,
+    // position in characters, not bytes, so the error pointer lines up under "°"
+    start: usize,
+    len: usize,
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match &self.kind {
+            TokenKind::Number(value) => format!("number `{value}`"),
+            TokenKind::Word(word) => format!("`{word}`"),
+            TokenKind::Arrow => String::from("`->`"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParseErrorKind {
+    UnexpectedCharacter(char),
+    ExpectedNumber { found: Option<String> },
+    ExpectedUnit { found: Option<String> },
+    UnknownUnit(String),
+    ExpectedConnector { found: String },
+    // a valid unit that cannot be converted to, e.g. kilograms from meters
+    Units(UnitError),
+    // the expression was complete but more followed
+    TrailingInput { found: String },
+}
+
+// What went wrong and where. `position` and `len` count characters of the input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    pub kind: ParseErrorKind,
+    pub position: usize,
+    pub len: usize,
+}
+
+impl ParseError {
+    // The input with a marker under the bad token:
+    //
+    //   100 F on C
+    //         ^^
+    pub fn pointer(&self, input: &str) -> String {
+        format!(
+            "{input}\n{}{}",
+            " ".repeat(self.position),
+            "^".repeat(self.len.max(1))
+        )
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let column = self.position + 1;
+
+        match &self.kind {
+            ParseErrorKind::UnexpectedCharacter(c) => {
+                write!(f, "unexpected character `{c}` at column {column}")
+            }
+            ParseErrorKind::ExpectedNumber { found: Some(found) } => {
+                write!(f, "expected a number at column {column}, found {found}")
+            }
+            ParseErrorKind::ExpectedNumber { found: None } => {
+                write!(f, "expected a number, found nothing")
+            }
+            ParseErrorKind::ExpectedUnit { found: Some(found) } => {
+                write!(f, "expected a unit at column {column}, found {found}")
+            }
+            ParseErrorKind::ExpectedUnit { found: None } => {
+                write!(f, "expected a unit at column {column}, like C, F or K")
+            }
+            ParseErrorKind::UnknownUnit(unit) => {
+                write!(f, "unknown unit `{unit}` at column {column}")
+            }
+            ParseErrorKind::ExpectedConnector { found } => write!(
+                f,
+                "expected `in`, `to` or `as` at column {column}, found {found}"
+            ),
+            ParseErrorKind::TrailingInput { found } => write!(
+                f,
+                "unexpected {found} at column {column}, the conversion is already complete"
+            ),
+            ParseErrorKind::Units(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Expression {
+    pub quantity: Quantity,
+    // `None` when only a quantity was written, e.g. "98.6F"
+    pub to: Option<&'static Unit>,
+}
+
+impl Expression {
+    // Numbers are read the way `locale` writes them, see `parse_number`.
+    pub fn parse(input: &str, locale: Locale) -> Result<Expression, ParseError> {
+        let tokens = tokenize(input, locale)?;
+        let mut parser = Parser {
+            tokens,
+            next: 0,
+            end: input.chars().count(),
+        };
+
+        let (value, number) = parser.number()?;
+        let (unit, _) = parser.source_unit()?;
+
+        // e.g. a temperature below absolute zero
+        let quantity = Quantity::new(value, unit).map_err(|error| ParseError {
+            kind: ParseErrorKind::Units(error),
+            position: number.start,
+            len: number.len,
+        })?;
+
+        let to = match parser.peek() {
+            None => None,
+            Some(token) if is_connector(token) => {
+                parser.next += 1;
+                let (to, token) = parser.unit()?;
+
+                if to.dimension != unit.dimension {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::Units(UnitError::Dimension { from: unit, to }),
+                        position: token.start,
+                        len: token.len,
+                    });
+                }
+
+                Some(to)
+            }
+            Some(token) => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::ExpectedConnector {
+                        found: token.describe(),
+                    },
+                    position: token.start,
+                    len: token.len,
+                })
+            }
+        };
+
+        // anything after the target unit is one token too many
+        if let Some(token) = parser.peek() {
+            return Err(ParseError {
+                kind: ParseErrorKind::TrailingInput {
+                    found: token.describe(),
+                },
+                position: token.start,
+                len: token.len,
+            });
+        }
+
+        Ok(Expression { quantity, to })
+    }
+}
+
+fn tokenize(input: &str, locale: Locale) -> Result<Vec<Token>, ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut tokens = Vec::new();
+    let mut index = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+        let start = index;
+
+        if c.is_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        let kind = if c == '-' && chars.get(index + 1) == Some(&'>') {
+            index += 2;
+            TokenKind::Arrow
+        } else if c.is_ascii_digit()
+            || c == '.'
+            || ((c == '-' || c == '+')
+                && chars
+                    .get(index + 1)
+                    .is_some_and(|next| next.is_ascii_digit() || *next == '.'))
+        {
+            // a comma followed by a digit belongs to the number, "1,000 m" or "36,6 C" depending on the locale
+            index += 1;
+            while index < chars.len()
+                && (chars[index].is_ascii_digit()
+                    || chars[index] == '.'
+                    || (chars[index] == ','
+                        && chars.get(index + 1).is_some_and(char::is_ascii_digit)))
+            {
+                index += 1;
+            }
+
+            let text: String = chars[start..index].iter().collect();
+            match parse_number(&text, locale) {
+                Some(value) => TokenKind::Number(value),
+                None => {
+                    return Err(ParseError {
+                        kind: ParseErrorKind::ExpectedNumber {
+                            found: Some(format!("`{text}`")),
+                        },
+                        position: start,
+                        len: index - start,
+                    })
+                }
+            }
+        } else if c.is_alphabetic() || c == '°' {
+            // "°C" is one token, a lone "°" is read like the word "degrees",
+            // after the first letter digits and "/" belong to the unit too, as in "km/h" or "m3"
+            index += 1;
+            while index < chars.len()
+                && (chars[index].is_alphanumeric() || chars[index] == '/' || chars[index] == '³')
+            {
+                index += 1;
+            }
+
+            TokenKind::Word(chars[start..index].iter().collect())
+        } else {
+            return Err(ParseError {
+                kind: ParseErrorKind::UnexpectedCharacter(c),
+                position: start,
+                len: 1,
+            });
+        };
+
+        tokens.push(Token {
+            kind,
+            start,
+            len: index - start,
+        });
+    }
+
+    Ok(tokens)
+}
+
+fn is_connector(token: &Token) -> bool {
+    match &token.kind {
+        TokenKind::Arrow => true,
+        TokenKind::Word(word) => {
+            matches!(word.to_lowercase().as_str(), "in" | "to" | "as" | "into")
+        }
+        TokenKind::Number(_) => false,
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    next: usize,
+    // length of the input, where "found nothing" errors point
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.next)
+    }
+
+    fn number(&mut self) -> Result<(f64, Token), ParseError> {
+        match self.peek().cloned() {
+            Some(token) => match token.kind {
+                TokenKind::Number(value) => {
+                    self.next += 1;
+                    Ok((value, token))
+                }
+                _ => Err(ParseError {
+                    kind: ParseErrorKind::ExpectedNumber {
+                        found: Some(token.describe()),
+                    },
+                    position: token.start,
+                    len: token.len,
+                }),
+            },
+            None => Err(ParseError {
+                kind: ParseErrorKind::ExpectedNumber { found: None },
+                position: self.end,
+                len: 1,
+            }),
+        }
+    }
+
+    fn word(&self, offset: usize) -> Option<String> {
+        match self.tokens.get(self.next + offset).map(|token| &token.kind) {
+            Some(TokenKind::Word(word)) => Some(word.to_lowercase()),
+            _ => None,
+        }
+    }
+
+    // "degrees" and a lone "°" only make the sentence read well
+    fn skip_degrees(&mut self) {
+        if matches!(
+            self.word(0).as_deref(),
+            Some("degree" | "degrees" | "deg" | "°")
+        ) {
+            self.next += 1;
+        }
+    }
+
+    // The unit right after the number. "in" is a connector and inches too:
+    // - "12 in", "12 in to cm" and "12 in in cm" are inches
+    // - "12 in cm" converts inches to cm, the "in" is left for the caller to read again as the connector
+    // - "100 degrees in C" is Celsius, nobody converts inches to a temperature
+    fn source_unit(&mut self) -> Result<(&'static Unit, Token), ParseError> {
+        self.skip_degrees();
+
+        if self.word(0).as_deref() == Some("in")
+            && self
+                .tokens
+                .get(self.next + 1)
+                .is_some_and(|next| matches!(next.kind, TokenKind::Word(_)) && !is_connector(next))
+        {
+            match self.lookup(self.next + 1) {
+                Some((unit, _, _)) if unit.dimension == Dimension::Length => {
+                    let token = self.tokens[self.next].clone();
+                    return Ok((Unit::find("in").unwrap(), token));
+                }
+                _ => self.next += 1,
+            }
+        }
+
+        self.unit()
+    }
+
+    // The unit written with the most words from token `at` on, so "nautical miles" is not read as
+    // "nautical" followed by miles. Returns it with one token covering all of its words and their count.
+    fn lookup(&self, at: usize) -> Option<(&'static Unit, Token, usize)> {
+        let words: Vec<(&Token, &str)> = self.tokens[at..]
+            .iter()
+            .map_while(|token| match &token.kind {
+                TokenKind::Word(word) => Some((token, word.as_str())),
+                _ => None,
+            })
+            .collect();
+
+        (1..=words.len()).rev().find_map(|count| {
+            let text = words[..count]
+                .iter()
+                .map(|(_, word)| *word)
+                .collect::<Vec<_>>()
+                .join(" ");
+            let unit = Unit::find(&text)?;
+
+            let (first, last) = (words[0].0, words[count - 1].0);
+            let token = Token {
+                kind: TokenKind::Word(text),
+                start: first.start,
+                len: last.start + last.len - first.start,
+            };
+            Some((unit, token, count))
+        })
+    }
+
+    fn unit(&mut self) -> Result<(&'static Unit, Token), ParseError> {
+        self.skip_degrees();
+
+        let token = match self.peek().cloned() {
+            Some(token) => token,
+            None => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::ExpectedUnit { found: None },
+                    position: self.end,
+                    len: 1,
+                })
+            }
+        };
+
+        let word = match &token.kind {
+            // after a connector "in" can only be inches
+            TokenKind::Word(word) => word,
+            _ => {
+                return Err(ParseError {
+                    kind: ParseErrorKind::ExpectedUnit {
+                        found: Some(token.describe()),
+                    },
+                    position: token.start,
+                    len: token.len,
+                })
+            }
+        };
+
+        let (unit, token, count) = self.lookup(self.next).ok_or_else(|| ParseError {
+            kind: ParseErrorKind::UnknownUnit(word.clone()),
+            position: token.start,
+            len: token.len,
+        })?;
+
+        self.next += count;
+        Ok((unit, token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expression(value: f64, from: &str, to: Option<&str>) -> Expression {
+        Expression {
+            quantity: Quantity::new(value, Unit::find(from).unwrap()).unwrap(),
+            to: to.map(|to| Unit::find(to).unwrap()),
+        }
+    }
+
+    #[test]
+    fn sentences_are_parsed() {
+        assert_eq!(
+            Expression::parse("100 F in C", Locale::Point),
+            Ok(expression(100.0, "F", Some("C")))
+        );
+        assert_eq!(
+            Expression::parse("-40°C to kelvin", Locale::Point),
+            Ok(expression(-40.0, "C", Some("K")))
+        );
+        assert_eq!(
+            Expression::parse("300K as F", Locale::Point),
+            Ok(expression(300.0, "K", Some("F")))
+        );
+        assert_eq!(
+            Expression::parse("36,6 C in F", Locale::Comma),
+            Ok(expression(36.6, "C", Some("F")))
+        );
+        assert_eq!(
+            Expression::parse("98.6 degrees Fahrenheit", Locale::Point),
+            Ok(expression(98.6, "F", None))
+        );
+        assert_eq!(
+            Expression::parse("0 ° C -> kelvins", Locale::Point),
+            Ok(expression(0.0, "C", Some("K")))
+        );
+    }
+
+    #[test]
+    fn other_units_are_parsed() {
+        assert_eq!(
+            Expression::parse("5 km in miles", Locale::Point),
+            Ok(expression(5.0, "km", Some("mi")))
+        );
+        assert_eq!(
+            Expression::parse("12 in to cm", Locale::Point),
+            Ok(expression(12.0, "in", Some("cm")))
+        );
+        assert_eq!(
+            Expression::parse("100km/h as m/s", Locale::Point),
+            Ok(expression(100.0, "km/h", Some("m/s")))
+        );
+        assert_eq!(
+            Expression::parse("5 in in cm", Locale::Point),
+            Ok(expression(5.0, "in", Some("cm")))
+        );
+        assert_eq!(
+            Expression::parse("12 in", Locale::Point),
+            Ok(expression(12.0, "in", None))
+        );
+        assert_eq!(
+            Expression::parse("12 in cm", Locale::Point),
+            Ok(expression(12.0, "in", Some("cm")))
+        );
+    }
+
+    #[test]
+    fn unit_names_can_be_several_words() {
+        assert_eq!(
+            Expression::parse("3 nautical miles to km", Locale::Point),
+            Ok(expression(3.0, "nmi", Some("km")))
+        );
+        assert_eq!(
+            Expression::parse("2 fluid ounces in ml", Locale::Point),
+            Ok(expression(2.0, "floz", Some("ml")))
+        );
+        assert_eq!(
+            Expression::parse("30 psi as pounds per square inch", Locale::Point),
+            Ok(expression(30.0, "psi", Some("psi")))
+        );
+        assert_eq!(
+            Expression::parse("1 in in nautical miles", Locale::Point),
+            Ok(expression(1.0, "in", Some("nmi")))
+        );
+
+        let error = Expression::parse("3 nautical miles to km now", Locale::Point).unwrap_err();
+        assert_eq!((error.position, error.len), (23, 3));
+        let error = Expression::parse("3 m to nautical", Locale::Point).unwrap_err();
+        assert_eq!(
+            error.kind,
+            ParseErrorKind::UnknownUnit(String::from("nautical"))
+        );
+    }
+
+    #[test]
+    fn in_before_a_unit_is_a_connector() {
+        assert_eq!(
+            Expression::parse("100 degrees in C", Locale::Point),
+            Ok(expression(100.0, "C", None))
+        );
+        assert_eq!(
+            Expression::parse("100 in Fahrenheit to C", Locale::Point),
+            Ok(expression(100.0, "F", Some("C")))
+        );
+    }
+
+    #[test]
+    fn commas_follow_the_locale() {
+        assert_eq!(
+            Expression::parse("1,000 m to km", Locale::Point),
+            Ok(expression(1000.0, "m", Some("km")))
+        );
+        assert_eq!(
+            Expression::parse("1,234.5 ft", Locale::Point),
+            Ok(expression(1234.5, "ft", None))
+        );
+        assert_eq!(
+            Expression::parse("1,000 m to km", Locale::Comma),
+            Ok(expression(1.0, "m", Some("km")))
+        );
+
+        let error = Expression::parse("36,6 C", Locale::Point).unwrap_err();
+        assert_eq!((error.position, error.len), (0, 4));
+    }
+
+    #[test]
+    fn different_dimensions_point_at_the_target() {
+        let error = Expression::parse("3 m to kg", Locale::Point).unwrap_err();
+
+        assert_eq!((error.position, error.len), (7, 2));
+        assert_eq!(
+            error.to_string(),
+            "cannot convert m to kg: m is a length unit and kg is a mass unit"
+        );
+    }
+
+    #[test]
+    fn errors_point_at_the_bad_token() {
+        let error = Expression::parse("100 F on C", Locale::Point).unwrap_err();
+        assert_eq!(error.position, 6);
+        assert_eq!(error.len, 2);
+        assert_eq!(
+            error.to_string(),
+            "expected `in`, `to` or `as` at column 7, found `on`"
+        );
+        assert_eq!(error.pointer("100 F on C"), "100 F on C\n      ^^");
+
+        let error = Expression::parse("100 X in C", Locale::Point).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnknownUnit(String::from("X")));
+        assert_eq!(error.position, 4);
+
+        let error = Expression::parse("hot in C", Locale::Point).unwrap_err();
+        assert_eq!(error.position, 0);
+
+        let error = Expression::parse("100 F in", Locale::Point).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::ExpectedUnit { found: None });
+        assert_eq!(error.position, 8);
+
+        let error = Expression::parse("100 F in C!", Locale::Point).unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::UnexpectedCharacter('!'));
+        assert_eq!(error.position, 10);
+
+        let error = Expression::parse("100 F in C please", Locale::Point).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "unexpected `please` at column 12, the conversion is already complete"
+        );
+
+        let error = Expression::parse("1.2.3 C", Locale::Point).unwrap_err();
+        assert_eq!(error.len, 5);
+    }
+
+    #[test]
+    fn impossible_temperatures_point_at_the_number() {
+        let error = Expression::parse("°C -1", Locale::Point).unwrap_err();
+        assert_eq!(error.position, 0);
+
+        let error = Expression::parse("-300 °C in F", Locale::Point).unwrap_err();
+        assert!(matches!(
+            error.kind,
+            ParseErrorKind::Units(UnitError::Temperature(_))
+        ));
+        assert_eq!((error.position, error.len), (0, 4));
+    }
+}
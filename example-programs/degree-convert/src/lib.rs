@@ -0,0 +1,16 @@
+// Conversion logic lives in this library crate so it can be tested, `main.rs` only talks to the user.
+pub mod batch;
+pub mod config;
+pub mod expression;
+pub mod number;
+pub mod table;
+pub mod temperature;
+pub mod units;
+
+pub use batch::{Batch, Report, RowError};
+pub use config::{Config, Mode};
+pub use expression::{Expression, ParseError};
+pub use number::{Locale, NumberFormat, Precision, Rounding};
+pub use table::{Format, Table, TableError};
+pub use temperature::{Scale, Temperature, TemperatureError};
+pub use units::{Dimension, Quantity, Unit, UnitError};
@@ -1,27 +1,157 @@
-use std::io::stdin;
+use std::fs::File;
+use std::io::{self, stdin, BufRead, BufReader};
+use std::path::PathBuf;
+use std::{env, process};
+
+use degree_convert::number::parse_number;
+use degree_convert::{Batch, Config, Mode, NumberFormat, Quantity, Scale, Temperature, Unit};
 
 fn main() {
-    println!("Enter temperature: ");
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    match config.mode {
+        // `Config::build` makes sure `--to` is a temperature scale here
+        Mode::Interactive => interactive(config.to.and_then(|unit| unit.scale()), &config.numbers),
+        Mode::Convert(quantity) => show(&quantity, config.to, &config.numbers),
+        Mode::Table { ref table, format } => match table.render(format, &config.numbers) {
+            Ok(rendered) => print!("{rendered}"),
+            Err(error) => {
+                eprintln!("Could not make the table: {error}");
+                process::exit(1);
+            }
+        },
+        Mode::Batch {
+            ref input,
+            column,
+            from,
+            header,
+        } => batch(
+            input,
+            Batch {
+                column,
+                from,
+                to: config.to,
+                header,
+                numbers: config.numbers,
+            },
+        ),
+    }
+}
+
+// Prints the quantity in `to`, or in its counterpart for temperatures.
+// Without either every other unit of the same dimension is listed.
+fn show(quantity: &Quantity, to: Option<&'static Unit>, numbers: &NumberFormat) {
+    let targets: Vec<&'static Unit> = match to.or(quantity.counterpart()) {
+        Some(to) => vec![to],
+        None => Unit::of(quantity.unit.dimension)
+            .filter(|unit| *unit != quantity.unit)
+            .collect(),
+    };
+
+    for target in targets {
+        match quantity.to(target) {
+            Ok(converted) => println!("{}", converted.format(numbers)),
+            Err(error) => {
+                eprintln!("{error}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+fn interactive(to: Option<Scale>, numbers: &NumberFormat) {
+    let temperature = loop {
+        let Some(value) = ask("Enter temperature: ") else {
+            return;
+        };
+
+        // "36,6" with `--locale de`, "36.6" otherwise
+        let value = match parse_number(&value, numbers.locale) {
+            Some(value) => value,
+            None => {
+                println!("Please enter number.");
+                continue;
+            }
+        };
 
-    let mut temperature = String::new();
+        let Some(degree) = ask("Enter degree (C, F, K, Ra, Re or De): ") else {
+            return;
+        };
 
-    stdin()
-        .read_line(&mut temperature)
-        .expect("There was an error with your input.");
+        let scale: Scale = match degree.parse() {
+            Ok(scale) => scale,
+            Err(error) => {
+                println!("Invalid degree type: {error}");
+                continue;
+            }
+        };
 
-    let temperature: f64 = temperature.trim().parse().expect("Please enter number.");
+        match Temperature::new(value, scale) {
+            Ok(temperature) => break temperature,
+            Err(error) => println!("Invalid temperature: {error}"),
+        }
+    };
 
-    let mut degree = String::new();
+    let target = to.unwrap_or(temperature.scale().counterpart());
+    println!("It is {}", temperature.to(target).format(numbers));
 
-    println!("Enter degree: ");
+    if to.is_none() {
+        for other in Scale::ALL {
+            if other != temperature.scale() && other != target {
+                println!("  = {}", temperature.to(other).format(numbers));
+            }
+        }
+    }
+}
+
+// `None` when stdin is closed or cannot be read, there is nothing left to ask then.
+fn ask(question: &str) -> Option<String> {
+    println!("{question}");
+
+    let mut answer = String::new();
+
+    match stdin().read_line(&mut answer) {
+        Ok(0) => None,
+        Ok(_) => Some(answer),
+        Err(error) => {
+            println!("There was an error with your input: {error}");
+            None
+        }
+    }
+}
+
+fn batch(input: &Option<PathBuf>, batch: Batch) {
+    let input: Box<dyn BufRead> = match input {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => {
+                eprintln!("Could not open {}: {error}", path.display());
+                process::exit(1);
+            }
+        },
+        None => Box::new(stdin().lock()),
+    };
 
-    stdin()
-        .read_line(&mut degree)
-        .expect("There was an error with your input.");
+    let report = batch
+        .run(input, &mut io::stdout().lock())
+        .unwrap_or_else(|err| {
+            eprintln!("Batch conversion failed: {err}");
+            process::exit(1);
+        });
+
+    // Bad rows go to stderr, so the converted rows on stdout can be piped on unchanged.
+    for error in &report.skipped {
+        eprintln!("Skipped {error}");
+    }
 
-    match degree.trim().to_uppercase().as_str() {
-        "F" => println!("It is {}°C", (temperature - 32.0) * 5.0 / 9.0),
-        "C" => println!("It is {}°F", (temperature * (9.0 / 5.0)) + 32.0),
-        _ => println!("Invalid degree type please enter C or F"),
+    if !report.skipped.is_empty() {
+        eprintln!(
+            "Converted {} rows, skipped {}.",
+            report.converted,
+            report.skipped.len()
+        );
     }
 }
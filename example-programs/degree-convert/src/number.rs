@@ -0,0 +1,430 @@
+use std::fmt;
+use std::str::FromStr;
+
+// Significant digits used when no precision is asked for. Enough for any real measurement
+// while hiding float noise like the 4 at the end of 0.30000000000000004.
+const SHORTEST_DIGITS: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    // as few digits as needed, e.g. "37.7777777778" and "212"
+    Shortest,
+    // always this many digits after the decimal separator, e.g. "37.78" and "212.00"
+    Decimals(usize),
+    // this many digits counted from the first one that is not zero, e.g. "37.8" and "0.000123"
+    Significant(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    // ties go to the even digit, 2.5 -> 2 and 3.5 -> 4, so rounding many values does not drift up
+    HalfEven,
+    // ties go away from zero, 2.5 -> 3, the way most people round by hand
+    HalfUp,
+    // extra digits are cut off, 2.9 -> 2
+    Truncate,
+}
+
+impl fmt::Display for Rounding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Rounding::HalfEven => "half-even",
+            Rounding::HalfUp => "half-up",
+            Rounding::Truncate => "truncate",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Rounding {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Rounding, String> {
+        match name.trim().to_lowercase().as_str() {
+            "half-even" | "even" | "bankers" => Ok(Rounding::HalfEven),
+            "half-up" | "up" => Ok(Rounding::HalfUp),
+            "truncate" | "down" => Ok(Rounding::Truncate),
+            _ => Err(format!(
+                "unknown rounding `{name}`, expected half-even, half-up or truncate"
+            )),
+        }
+    }
+}
+
+// Which character separates the whole part from the fraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    // 36.6, fields in CSV are separated by ","
+    Point,
+    // 36,6, fields in CSV are separated by ";" like spreadsheets in most of Europe do
+    Comma,
+}
+
+impl Locale {
+    pub fn decimal_separator(&self) -> char {
+        match self {
+            Locale::Point => '.',
+            Locale::Comma => ',',
+        }
+    }
+
+    // groups thousands, it is the decimal separator of the other locale
+    pub fn group_separator(&self) -> char {
+        match self {
+            Locale::Point => ',',
+            Locale::Comma => '.',
+        }
+    }
+
+    pub fn field_separator(&self) -> char {
+        match self {
+            Locale::Point => ',',
+            Locale::Comma => ';',
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    // Takes the separator itself or a language code, "de_DE.UTF-8" style names work too.
+    fn from_str(name: &str) -> Result<Locale, String> {
+        let language = name
+            .trim()
+            .to_lowercase()
+            .split(['_', '-', '.'])
+            .next()
+            .unwrap_or("")
+            .to_string();
+
+        match language.as_str() {
+            "point" | "dot" | "c" | "posix" | "en" | "ja" | "zh" | "ko" | "he" | "th" => {
+                Ok(Locale::Point)
+            }
+            "comma" | "de" | "fr" | "es" | "it" | "nl" | "pt" | "ru" | "tr" | "pl" | "sv"
+            | "da" | "fi" | "nb" | "no" | "cs" | "el" | "uk" => Ok(Locale::Comma),
+            _ => Err(format!(
+                "unknown locale `{name}`, use `point` or `comma` or a language like en or de"
+            )),
+        }
+    }
+}
+
+// How numbers are printed: how many digits, how they are rounded and which decimal separator is used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberFormat {
+    pub precision: Precision,
+    pub rounding: Rounding,
+    pub locale: Locale,
+}
+
+impl Default for NumberFormat {
+    fn default() -> NumberFormat {
+        NumberFormat {
+            precision: Precision::Shortest,
+            rounding: Rounding::HalfEven,
+            locale: Locale::Point,
+        }
+    }
+}
+
+impl NumberFormat {
+    pub fn format(&self, value: f64) -> String {
+        if !value.is_finite() {
+            return value.to_string();
+        }
+
+        let text = match self.precision {
+            Precision::Decimals(decimals) => round(value, Keep::Decimals(decimals), self.rounding),
+            Precision::Significant(digits) => {
+                round(value, Keep::Significant(digits.max(1)), self.rounding)
+            }
+            Precision::Shortest => {
+                let text = round(value, Keep::Significant(SHORTEST_DIGITS), self.rounding);
+
+                match text.contains('.') {
+                    true => text.trim_end_matches('0').trim_end_matches('.').to_string(),
+                    false => text,
+                }
+            }
+        };
+
+        text.replace('.', &self.locale.decimal_separator().to_string())
+    }
+}
+
+// Reads a number written the way `locale` writes them. The other separator may group thousands,
+// "1,000.5" with a point and "1.000,5" with a comma, but only in groups of three in front of
+// the decimal mark. Anything else is refused instead of guessed, "1,000" is never read as 1.
+pub fn parse_number(text: &str, locale: Locale) -> Option<f64> {
+    let text = text.trim();
+    let (whole, fraction) = match text.split_once(locale.decimal_separator()) {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (text, None),
+    };
+
+    let digits = whole.trim_start_matches(['-', '+']);
+    let group = locale.group_separator();
+
+    if digits.contains(group) {
+        let mut groups = digits.split(group);
+        let first = groups.next().unwrap_or("");
+        let all_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+
+        if !(1..=3).contains(&first.len())
+            || !all_digits(first)
+            || !groups.all(|part| part.len() == 3 && all_digits(part))
+        {
+            return None;
+        }
+    }
+
+    let mut plain = whole.replace(group, "");
+    if let Some(fraction) = fraction {
+        plain.push('.');
+        plain.push_str(fraction);
+    }
+
+    let value: f64 = plain.parse().ok()?;
+    value.is_finite().then_some(value)
+}
+
+#[derive(Clone, Copy)]
+enum Keep {
+    Decimals(usize),
+    Significant(usize),
+}
+
+// Rounds on the decimal digits of the shortest text that reads back as `value`, not on the binary value.
+// That way 2.675 rounds to 2.68 with half-up, like it does on paper, even though the f64 is a hair below.
+fn round(value: f64, keep: Keep, rounding: Rounding) -> String {
+    // `Display` of f64 never uses an exponent, so this is always plain digits with an optional point
+    let text = value.abs().to_string();
+    let (whole, fraction) = text.split_once('.').unwrap_or((&text, ""));
+
+    let mut digits: Vec<u8> = whole
+        .bytes()
+        .chain(fraction.bytes())
+        .map(|b| b - b'0')
+        .collect();
+    // digits in front of the decimal point
+    let mut point = whole.len();
+
+    let first = digits.iter().position(|digit| *digit != 0);
+    let length = match (keep, first) {
+        (Keep::Decimals(decimals), _) => point.saturating_add(decimals),
+        (Keep::Significant(significant), Some(first)) => first.saturating_add(significant),
+        // zero has no significant digits, it stays a plain 0
+        (Keep::Significant(_), None) => point,
+    };
+
+    if digits.len() < length {
+        digits.resize(length, 0);
+    }
+
+    let (kept, dropped) = digits.split_at(length);
+    let mut kept = kept.to_vec();
+
+    let round_up = match (rounding, dropped.split_first()) {
+        (_, None) | (Rounding::Truncate, _) => false,
+        (Rounding::HalfUp, Some((first, _))) => *first >= 5,
+        (Rounding::HalfEven, Some((first, rest))) => {
+            let odd = kept.last().is_some_and(|digit| digit % 2 == 1);
+            *first > 5 || (*first == 5 && (rest.iter().any(|digit| *digit != 0) || odd))
+        }
+    };
+
+    if round_up {
+        // carry from the last kept digit to the front, 9.99 -> 10.00
+        let mut index = kept.len();
+        loop {
+            if index == 0 {
+                kept.insert(0, 1);
+                point += 1;
+                break;
+            }
+
+            index -= 1;
+            if kept[index] == 9 {
+                kept[index] = 0;
+            } else {
+                kept[index] += 1;
+                break;
+            }
+        }
+    }
+
+    // a carry can move the first digit to the left, 0.0996 -> 0.100, which is one significant digit too many
+    if let Keep::Significant(significant) = keep {
+        let first = kept.iter().position(|digit| *digit != 0).unwrap_or(0);
+        kept.truncate(first.saturating_add(significant).max(point));
+    }
+
+    // few significant digits of a big number, 1234 -> 1200
+    if kept.len() < point {
+        kept.resize(point, 0);
+    }
+
+    let whole: String = kept[..point]
+        .iter()
+        .map(|digit| (b'0' + digit) as char)
+        .collect();
+    let fraction: String = kept[point..]
+        .iter()
+        .map(|digit| (b'0' + digit) as char)
+        .collect();
+
+    // "-0.00" reads like a bug, rounding away everything leaves a plain 0
+    let sign = match value < 0.0 && kept.iter().any(|digit| *digit != 0) {
+        true => "-",
+        false => "",
+    };
+
+    match fraction.is_empty() {
+        true => format!("{sign}{whole}"),
+        false => format!("{sign}{whole}.{fraction}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn format(value: f64, precision: Precision, rounding: Rounding) -> String {
+        NumberFormat {
+            precision,
+            rounding,
+            locale: Locale::Point,
+        }
+        .format(value)
+    }
+
+    #[test]
+    fn shortest_hides_float_noise() {
+        let numbers = NumberFormat::default();
+
+        assert_eq!(numbers.format(37.77777777777778), "37.7777777778");
+        assert_eq!(numbers.format(0.1 + 0.2), "0.3");
+        assert_eq!(numbers.format(211.99999999999994), "212");
+        assert_eq!(numbers.format(-459.66999999999996), "-459.67");
+        assert_eq!(
+            numbers.format(1.602176634e-19),
+            "0.0000000000000000001602176634"
+        );
+        assert_eq!(numbers.format(0.0), "0");
+    }
+
+    #[test]
+    fn decimal_places() {
+        assert_eq!(
+            format(37.77777, Precision::Decimals(2), Rounding::HalfEven),
+            "37.78"
+        );
+        assert_eq!(
+            format(212.0, Precision::Decimals(2), Rounding::HalfEven),
+            "212.00"
+        );
+        assert_eq!(
+            format(9.999, Precision::Decimals(2), Rounding::HalfUp),
+            "10.00"
+        );
+        assert_eq!(
+            format(-0.001, Precision::Decimals(2), Rounding::HalfUp),
+            "0.00"
+        );
+        assert_eq!(
+            format(1234.5, Precision::Decimals(0), Rounding::HalfEven),
+            "1234"
+        );
+    }
+
+    #[test]
+    fn significant_digits() {
+        assert_eq!(
+            format(37.77777, Precision::Significant(3), Rounding::HalfEven),
+            "37.8"
+        );
+        assert_eq!(
+            format(1234.5, Precision::Significant(2), Rounding::HalfEven),
+            "1200"
+        );
+        assert_eq!(
+            format(0.000123456, Precision::Significant(3), Rounding::HalfEven),
+            "0.000123"
+        );
+        assert_eq!(
+            format(9.96, Precision::Significant(2), Rounding::HalfUp),
+            "10"
+        );
+        assert_eq!(
+            format(0.0996, Precision::Significant(2), Rounding::HalfUp),
+            "0.10"
+        );
+        assert_eq!(
+            format(1.5, Precision::Significant(3), Rounding::HalfUp),
+            "1.50"
+        );
+    }
+
+    #[test]
+    fn rounding_modes_differ_on_ties() {
+        let decimals = |value, rounding| format(value, Precision::Decimals(0), rounding);
+
+        assert_eq!(decimals(2.5, Rounding::HalfEven), "2");
+        assert_eq!(decimals(3.5, Rounding::HalfEven), "4");
+        assert_eq!(decimals(2.5, Rounding::HalfUp), "3");
+        assert_eq!(decimals(-2.5, Rounding::HalfUp), "-3");
+        assert_eq!(decimals(2.9, Rounding::Truncate), "2");
+        assert_eq!(decimals(-2.9, Rounding::Truncate), "-2");
+
+        // rounding works on the digits people see, not on the binary value below them
+        assert_eq!(
+            format(2.675, Precision::Decimals(2), Rounding::HalfUp),
+            "2.68"
+        );
+        assert_eq!(
+            format(2.665, Precision::Decimals(2), Rounding::HalfEven),
+            "2.66"
+        );
+        assert_eq!(
+            format(2.6651, Precision::Decimals(2), Rounding::HalfEven),
+            "2.67"
+        );
+    }
+
+    #[test]
+    fn comma_locale() {
+        let numbers = NumberFormat {
+            precision: Precision::Decimals(1),
+            rounding: Rounding::HalfEven,
+            locale: "de_DE.UTF-8".parse().unwrap(),
+        };
+
+        assert_eq!(numbers.format(36.6), "36,6");
+        assert_eq!(numbers.locale.field_separator(), ';');
+        assert_eq!("en".parse(), Ok(Locale::Point));
+        assert!("xx".parse::<Locale>().is_err());
+    }
+
+    #[test]
+    fn numbers_are_read_in_the_locale() {
+        assert_eq!(parse_number(" 36.6 ", Locale::Point), Some(36.6));
+        assert_eq!(parse_number("-40", Locale::Point), Some(-40.0));
+        assert_eq!(parse_number("1,000", Locale::Point), Some(1000.0));
+        assert_eq!(parse_number("1,234.5", Locale::Point), Some(1234.5));
+        assert_eq!(
+            parse_number("-1,234,567", Locale::Point),
+            Some(-1_234_567.0)
+        );
+        assert_eq!(parse_number("36,6", Locale::Point), None);
+        assert_eq!(parse_number("1,2,3", Locale::Point), None);
+        assert_eq!(parse_number("1.5,000", Locale::Point), None);
+        assert_eq!(parse_number("inf", Locale::Point), None);
+
+        assert_eq!(parse_number("36,6", Locale::Comma), Some(36.6));
+        assert_eq!(parse_number("1.000", Locale::Comma), Some(1000.0));
+        assert_eq!(parse_number("1.234,5", Locale::Comma), Some(1234.5));
+        assert_eq!(parse_number("36.6", Locale::Comma), None);
+    }
+}
@@ -0,0 +1,380 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::number::{Locale, NumberFormat};
+use crate::units::{Quantity, Unit, UnitError};
+
+// Tables longer than this are almost certainly a typo in `--step`.
+pub const MAX_ROWS: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    // aligned columns for reading in a terminal
+    Text,
+    Csv,
+    Markdown,
+    Json,
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Format::Text => "text",
+            Format::Csv => "csv",
+            Format::Markdown => "markdown",
+            Format::Json => "json",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Format, String> {
+        match name.trim().to_lowercase().as_str() {
+            "text" | "txt" => Ok(Format::Text),
+            "csv" => Ok(Format::Csv),
+            "markdown" | "md" => Ok(Format::Markdown),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown table format `{name}`, expected text, csv, markdown or json"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TableError {
+    ZeroStep,
+    // e.g. from 0 to 100 with a step of -10, the end is never reached
+    WrongDirection { start: f64, end: f64, step: f64 },
+    TooManyRows(usize),
+    Units(UnitError),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TableError::ZeroStep => write!(f, "the step of a table cannot be 0"),
+            TableError::WrongDirection { start, end, step } => write!(
+                f,
+                "a step of {step} never gets from {start} to {end}, try {}",
+                -step
+            ),
+            TableError::TooManyRows(rows) => write!(
+                f,
+                "the table would have {rows} rows, at most {MAX_ROWS} are allowed"
+            ),
+            TableError::Units(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for TableError {}
+
+impl From<UnitError> for TableError {
+    fn from(error: UnitError) -> TableError {
+        TableError::Units(error)
+    }
+}
+
+// A reference sheet: `start..=end` by `step` in the `from` unit, converted into every other unit
+// of the same dimension, or only into `to` when one is given.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Table {
+    pub from: &'static Unit,
+    pub to: Option<&'static Unit>,
+    pub start: f64,
+    pub end: f64,
+    pub step: f64,
+}
+
+impl Table {
+    pub fn units(&self) -> Vec<&'static Unit> {
+        let mut units = vec![self.from];
+
+        match self.to {
+            Some(to) => units.push(to),
+            None => units.extend(Unit::of(self.from.dimension).filter(|unit| *unit != self.from)),
+        }
+
+        units
+    }
+
+    // One row per value, in the same order as `units`.
+    pub fn rows(&self) -> Result<Vec<Vec<f64>>, TableError> {
+        if self.step == 0.0 || !self.step.is_finite() {
+            return Err(TableError::ZeroStep);
+        }
+
+        let span = self.end - self.start;
+        if span != 0.0 && span.signum() != self.step.signum() {
+            return Err(TableError::WrongDirection {
+                start: self.start,
+                end: self.end,
+                step: self.step,
+            });
+        }
+
+        // counting steps instead of adding `step` over and over keeps 0.1 steps from drifting,
+        // the small slack lets the end itself in when it is a whole number of steps away
+        let count = (span / self.step + 1e-9).floor() + 1.0;
+        if count > MAX_ROWS as f64 {
+            return Err(TableError::TooManyRows(count as usize));
+        }
+
+        let units = self.units();
+
+        (0..count as usize)
+            .map(|index| {
+                let quantity = Quantity::new(self.start + index as f64 * self.step, self.from)?;
+
+                units
+                    .iter()
+                    .map(|unit| Ok(quantity.to(unit)?.value))
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn render(&self, format: Format, numbers: &NumberFormat) -> Result<String, TableError> {
+        // JSON numbers always use a decimal point, whatever the locale
+        let numbers = match format {
+            Format::Json => NumberFormat {
+                locale: Locale::Point,
+                ..*numbers
+            },
+            _ => *numbers,
+        };
+
+        let header: Vec<&str> = self.units().iter().map(|unit| unit.symbol).collect();
+        let rows: Vec<Vec<String>> = self
+            .rows()?
+            .iter()
+            .map(|row| row.iter().map(|value| numbers.format(*value)).collect())
+            .collect();
+
+        Ok(match format {
+            Format::Text => text(&header, &rows),
+            Format::Csv => csv(&header, &rows, numbers.locale.field_separator()),
+            Format::Markdown => markdown(&header, &rows),
+            Format::Json => json(&header, &rows),
+        })
+    }
+}
+
+// Numbers are right aligned so their decimal points line up.
+fn text(header: &[&str], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(column, name)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([name.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:>width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+    };
+
+    let mut lines = vec![line(header.to_vec())];
+    lines.push(
+        widths
+            .iter()
+            .map(|width| "-".repeat(*width))
+            .collect::<Vec<_>>()
+            .join("  "),
+    );
+    lines.extend(
+        rows.iter()
+            .map(|row| line(row.iter().map(String::as_str).collect())),
+    );
+
+    lines.join("\n") + "\n"
+}
+
+fn csv(header: &[&str], rows: &[Vec<String>], separator: char) -> String {
+    let separator = separator.to_string();
+
+    let mut lines = vec![header.join(&separator)];
+    lines.extend(rows.iter().map(|row| row.join(&separator)));
+
+    lines.join("\n") + "\n"
+}
+
+fn markdown(header: &[&str], rows: &[Vec<String>]) -> String {
+    let row = |cells: Vec<&str>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![row(header.to_vec())];
+    // `---:` right aligns the numbers
+    lines.push(row(vec!["---:"; header.len()]));
+    lines.extend(
+        rows.iter()
+            .map(|cells| row(cells.iter().map(String::as_str).collect())),
+    );
+
+    lines.join("\n") + "\n"
+}
+
+// An array with one object per row, keyed by unit symbol:
+// [{"°C": 0.00, "°F": 32.00}, ...]
+fn json(header: &[&str], rows: &[Vec<String>]) -> String {
+    let objects: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            let fields: Vec<String> = header
+                .iter()
+                .zip(row)
+                .map(|(name, value)| format!("{}: {value}", json_string(name)))
+                .collect();
+
+            format!("  {{{}}}", fields.join(", "))
+        })
+        .collect();
+
+    format!("[\n{}\n]\n", objects.join(",\n"))
+}
+
+fn json_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::number::{Precision, Rounding};
+
+    fn table(from: &str, to: Option<&str>, start: f64, end: f64, step: f64) -> Table {
+        Table {
+            from: Unit::find(from).unwrap(),
+            to: to.map(|to| Unit::find(to).unwrap()),
+            start,
+            end,
+            step,
+        }
+    }
+
+    fn decimals(decimals: usize) -> NumberFormat {
+        NumberFormat {
+            precision: Precision::Decimals(decimals),
+            ..NumberFormat::default()
+        }
+    }
+
+    #[test]
+    fn rows_cover_start_to_end() {
+        let rows = table("C", Some("F"), 0.0, 100.0, 25.0).rows().unwrap();
+
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[0], [0.0, 32.0]);
+        assert_eq!(rows[4], [100.0, 212.0]);
+
+        // 0.1 steps do not drift past the end
+        assert_eq!(
+            table("C", Some("K"), 0.0, 1.0, 0.1).rows().unwrap().len(),
+            11
+        );
+        assert_eq!(
+            table("C", Some("K"), 10.0, 0.0, -5.0).rows().unwrap().len(),
+            3
+        );
+    }
+
+    #[test]
+    fn every_other_unit_is_a_column() {
+        let table = table("C", None, 0.0, 10.0, 10.0);
+
+        let symbols: Vec<&str> = table.units().iter().map(|unit| unit.symbol).collect();
+        assert_eq!(symbols, ["°C", "°F", "K", "°Ra", "°Ré", "°De"]);
+    }
+
+    #[test]
+    fn bad_steps_are_refused() {
+        assert_eq!(
+            table("C", None, 0.0, 10.0, 0.0).rows(),
+            Err(TableError::ZeroStep)
+        );
+        assert!(matches!(
+            table("C", None, 0.0, 10.0, -1.0).rows(),
+            Err(TableError::WrongDirection { .. })
+        ));
+        assert!(matches!(
+            table("m", None, 0.0, 1e9, 1.0).rows(),
+            Err(TableError::TooManyRows(_))
+        ));
+        assert!(matches!(
+            table("C", None, -300.0, 0.0, 10.0).rows(),
+            Err(TableError::Units(_))
+        ));
+    }
+
+    #[test]
+    fn formats() {
+        let table = table("C", Some("F"), 0.0, 100.0, 100.0);
+
+        assert_eq!(
+            table.render(Format::Text, &decimals(1)).unwrap(),
+            "   °C     °F\n-----  -----\n  0.0   32.0\n100.0  212.0\n"
+        );
+        assert_eq!(
+            table.render(Format::Csv, &decimals(0)).unwrap(),
+            "°C,°F\n0,32\n100,212\n"
+        );
+        assert_eq!(
+            table.render(Format::Markdown, &decimals(0)).unwrap(),
+            "| °C | °F |\n| ---: | ---: |\n| 0 | 32 |\n| 100 | 212 |\n"
+        );
+        assert_eq!(
+            table.render(Format::Json, &decimals(2)).unwrap(),
+            "[\n  {\"°C\": 0.00, \"°F\": 32.00},\n  {\"°C\": 100.00, \"°F\": 212.00}\n]\n"
+        );
+    }
+
+    #[test]
+    fn precision_hides_float_noise() {
+        let table = table("F", Some("C"), 100.0, 100.0, 1.0);
+
+        assert_eq!(
+            table.render(Format::Csv, &decimals(2)).unwrap(),
+            "°F,°C\n100.00,37.78\n"
+        );
+
+        let truncated = NumberFormat {
+            rounding: Rounding::Truncate,
+            ..decimals(1)
+        };
+        assert_eq!(
+            table.render(Format::Csv, &truncated).unwrap(),
+            "°F,°C\n100.0,37.7\n"
+        );
+    }
+
+    #[test]
+    fn comma_locale_switches_csv_separator() {
+        let table = table("C", Some("F"), 36.6, 36.6, 1.0);
+        let numbers = NumberFormat {
+            locale: Locale::Comma,
+            ..decimals(1)
+        };
+
+        assert_eq!(
+            table.render(Format::Csv, &numbers).unwrap(),
+            "°C;°F\n36,6;97,9\n"
+        );
+        assert!(table
+            .render(Format::Json, &numbers)
+            .unwrap()
+            .contains("\"°C\": 36.6"));
+    }
+}
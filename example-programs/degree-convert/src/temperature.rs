@@ -0,0 +1,237 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::number::NumberFormat;
+use crate::units::{self, Unit};
+
+const ABSOLUTE_ZERO_CELSIUS: f64 = -273.15;
+
+// Temperature scales the converter knows about.
+// The factors and offsets between them live in the unit table of `units.rs`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Celsius,
+    Fahrenheit,
+    Kelvin,
+    Rankine,
+    Reaumur,
+    Delisle,
+}
+
+impl Scale {
+    pub const ALL: [Scale; 6] = [
+        Scale::Celsius,
+        Scale::Fahrenheit,
+        Scale::Kelvin,
+        Scale::Rankine,
+        Scale::Reaumur,
+        Scale::Delisle,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Scale::Celsius => "°C",
+            Scale::Fahrenheit => "°F",
+            // Kelvin is an absolute scale and is written without the degree sign
+            Scale::Kelvin => "K",
+            Scale::Rankine => "°Ra",
+            Scale::Reaumur => "°Ré",
+            Scale::Delisle => "°De",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Scale::Celsius => "Celsius",
+            Scale::Fahrenheit => "Fahrenheit",
+            Scale::Kelvin => "Kelvin",
+            Scale::Rankine => "Rankine",
+            Scale::Reaumur => "Réaumur",
+            Scale::Delisle => "Delisle",
+        }
+    }
+
+    // The row of the unit table this scale stands for, conversions are done by the unit engine.
+    pub fn unit(&self) -> &'static Unit {
+        Unit::find(self.symbol()).expect("every scale is in the unit table")
+    }
+
+    // Where a value goes when no target scale is given. Fahrenheit used to be the only choice
+    // besides Celsius, so Celsius still converts to it and everything else to Celsius.
+    pub fn counterpart(&self) -> Scale {
+        match self {
+            Scale::Celsius => Scale::Fahrenheit,
+            _ => Scale::Celsius,
+        }
+    }
+
+    pub fn absolute_zero(&self) -> f64 {
+        Scale::Celsius.convert(ABSOLUTE_ZERO_CELSIUS, *self)
+    }
+
+    fn convert(self, value: f64, to: Scale) -> f64 {
+        units::convert(value, self.unit(), to.unit()).expect("temperatures convert to each other")
+    }
+}
+
+impl fmt::Display for Scale {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Scale {
+    type Err = TemperatureError;
+
+    // Accepts the symbols with or without the degree sign and the full names, in any case.
+    // Units of other dimensions like "m" are not scales.
+    fn from_str(text: &str) -> Result<Scale, TemperatureError> {
+        Unit::find(text)
+            .and_then(|unit| unit.scale())
+            .ok_or_else(|| TemperatureError::UnknownScale(text.trim().to_string()))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TemperatureError {
+    UnknownScale(String),
+    // colder than absolute zero, which cannot exist
+    BelowAbsoluteZero { value: f64, scale: Scale },
+    // NaN and infinity are not temperatures
+    NotFinite,
+}
+
+impl fmt::Display for TemperatureError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TemperatureError::UnknownScale(name) => write!(
+                f,
+                "unknown temperature scale `{name}`, expected C, F, K, Ra, Re or De"
+            ),
+            TemperatureError::BelowAbsoluteZero { value, scale } => write!(
+                f,
+                "{value}{} is below absolute zero ({}{})",
+                scale.symbol(),
+                scale.absolute_zero(),
+                scale.symbol()
+            ),
+            TemperatureError::NotFinite => write!(f, "temperature must be a finite number"),
+        }
+    }
+}
+
+impl std::error::Error for TemperatureError {}
+
+// A temperature that can physically exist: never below absolute zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Temperature {
+    value: f64,
+    scale: Scale,
+}
+
+impl Temperature {
+    // Floating point math can land a hair below absolute zero, e.g. for -459.67°F.
+    // Anything closer to absolute zero than this is treated as absolute zero.
+    const TOLERANCE: f64 = 1e-9;
+
+    pub fn new(value: f64, scale: Scale) -> Result<Temperature, TemperatureError> {
+        if !value.is_finite() {
+            return Err(TemperatureError::NotFinite);
+        }
+
+        if scale.convert(value, Scale::Celsius) < ABSOLUTE_ZERO_CELSIUS - Temperature::TOLERANCE {
+            return Err(TemperatureError::BelowAbsoluteZero { value, scale });
+        }
+
+        Ok(Temperature { value, scale })
+    }
+
+    pub fn value(&self) -> f64 {
+        self.value
+    }
+
+    pub fn scale(&self) -> Scale {
+        self.scale
+    }
+
+    pub fn to(&self, scale: Scale) -> Temperature {
+        Temperature {
+            value: self.scale.convert(self.value, scale),
+            scale,
+        }
+    }
+
+    pub fn format(&self, numbers: &NumberFormat) -> String {
+        format!("{}{}", numbers.format(self.value), self.scale.symbol())
+    }
+}
+
+impl fmt::Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(&NumberFormat::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-9,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn boiling_water_on_every_scale() {
+        let boiling = Temperature::new(100.0, Scale::Celsius).unwrap();
+        let expected = [100.0, 212.0, 373.15, 671.67, 80.0, 0.0];
+
+        for (scale, value) in Scale::ALL.iter().zip(expected) {
+            assert_close(boiling.to(*scale).value(), value);
+        }
+    }
+
+    #[test]
+    fn every_pair_round_trips() {
+        for from in Scale::ALL {
+            for to in Scale::ALL {
+                let start = Temperature::new(36.6, Scale::Celsius).unwrap().to(from);
+                assert_close(start.to(to).to(from).value(), start.value());
+            }
+        }
+    }
+
+    #[test]
+    fn minus_forty_is_the_same_in_celsius_and_fahrenheit() {
+        let cold = Temperature::new(-40.0, Scale::Fahrenheit).unwrap();
+        assert_close(cold.to(Scale::Celsius).value(), -40.0);
+    }
+
+    #[test]
+    fn below_absolute_zero_is_refused() {
+        assert_eq!(
+            Temperature::new(-300.0, Scale::Celsius),
+            Err(TemperatureError::BelowAbsoluteZero {
+                value: -300.0,
+                scale: Scale::Celsius
+            })
+        );
+        assert!(Temperature::new(-1.0, Scale::Kelvin).is_err());
+        // Delisle is upside down, higher numbers are colder
+        assert!(Temperature::new(600.0, Scale::Delisle).is_err());
+        assert!(Temperature::new(-459.67, Scale::Fahrenheit).is_ok());
+        assert!(Temperature::new(f64::NAN, Scale::Celsius).is_err());
+    }
+
+    #[test]
+    fn scales_are_parsed() {
+        assert_eq!("f".parse(), Ok(Scale::Fahrenheit));
+        assert_eq!(" °C ".parse(), Ok(Scale::Celsius));
+        assert_eq!("kelvin".parse(), Ok(Scale::Kelvin));
+        assert_eq!("Ré".parse(), Ok(Scale::Reaumur));
+        assert!("X".parse::<Scale>().is_err());
+        assert!("km".parse::<Scale>().is_err());
+    }
+}
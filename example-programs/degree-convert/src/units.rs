@@ -0,0 +1,510 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+use crate::number::{parse_number, Locale, NumberFormat};
+use crate::temperature::{Scale, Temperature, TemperatureError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dimension {
+    Length,
+    Mass,
+    Volume,
+    Speed,
+    Pressure,
+    Energy,
+    Temperature,
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Dimension::Length => "length",
+            Dimension::Mass => "mass",
+            Dimension::Volume => "volume",
+            Dimension::Speed => "speed",
+            Dimension::Pressure => "pressure",
+            Dimension::Energy => "energy",
+            Dimension::Temperature => "temperature",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Unit {
+    pub symbol: &'static str,
+    pub name: &'static str,
+    // other ways to write it, plurals of names ending in "s" are accepted without listing them
+    pub aliases: &'static [&'static str],
+    pub dimension: Dimension,
+}
+
+impl Unit {
+    // Finds a unit by symbol, name or alias, ignoring a leading degree sign.
+    // Symbols are tried as written first, so "Cal" (the food calorie) and "cal" stay apart.
+    // Another casing is only accepted when it fits a single unit, and plurals only for names,
+    // otherwise "cs" would be read as Celsius and "ms" as meters.
+    pub fn find(text: &str) -> Option<&'static Unit> {
+        let text = text.trim();
+        let text = text.strip_prefix('°').unwrap_or(text);
+
+        let spellings = |unit: &'static Unit| {
+            let symbol = unit.symbol.strip_prefix('°').unwrap_or(unit.symbol);
+            std::iter::once(symbol).chain(unit.aliases.iter().copied())
+        };
+
+        if let Some(unit) = UNITS
+            .iter()
+            .find(|unit| spellings(unit).any(|spelling| spelling == text))
+        {
+            return Some(unit);
+        }
+
+        let wanted = text.to_lowercase();
+        let mut loose = UNITS
+            .iter()
+            .filter(|unit| spellings(unit).any(|spelling| spelling.to_lowercase() == wanted));
+
+        if let (Some(unit), None) = (loose.next(), loose.next()) {
+            return Some(unit);
+        }
+
+        // lowercase aliases of at least three letters are words like "metre" or "ton" and get plurals too
+        let named = |unit: &&Unit, wanted: &str| {
+            unit.name.to_lowercase() == wanted
+                || unit.aliases.iter().any(|alias| {
+                    alias.chars().count() >= 3
+                        && alias.chars().all(char::is_lowercase)
+                        && alias.to_lowercase() == wanted
+                })
+        };
+
+        UNITS.iter().find(|unit| named(unit, &wanted)).or_else(|| {
+            let singular = wanted.strip_suffix('s')?;
+            UNITS.iter().find(|unit| named(unit, singular))
+        })
+    }
+
+    // The temperature scale this unit stands for, `None` for every other dimension.
+    pub fn scale(&'static self) -> Option<Scale> {
+        Scale::ALL.into_iter().find(|scale| scale.unit() == self)
+    }
+
+    const fn new(
+        symbol: &'static str,
+        name: &'static str,
+        dimension: Dimension,
+        aliases: &'static [&'static str],
+    ) -> Unit {
+        Unit {
+            symbol,
+            name,
+            aliases,
+            dimension,
+        }
+    }
+
+    pub fn of(dimension: Dimension) -> impl Iterator<Item = &'static Unit> {
+        UNITS.iter().filter(move |unit| unit.dimension == dimension)
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.symbol)
+    }
+}
+
+pub static UNITS: &[Unit] = &[
+    Unit::new("m", "meter", Dimension::Length, &["metre"]),
+    Unit::new("km", "kilometer", Dimension::Length, &["kilometre"]),
+    Unit::new("cm", "centimeter", Dimension::Length, &["centimetre"]),
+    Unit::new("mm", "millimeter", Dimension::Length, &["millimetre"]),
+    Unit::new("in", "inch", Dimension::Length, &["inches"]),
+    Unit::new("ft", "foot", Dimension::Length, &["feet"]),
+    Unit::new("yd", "yard", Dimension::Length, &[]),
+    Unit::new("mi", "mile", Dimension::Length, &[]),
+    Unit::new("nmi", "nautical mile", Dimension::Length, &[]),
+    Unit::new("kg", "kilogram", Dimension::Mass, &["kilo"]),
+    Unit::new("g", "gram", Dimension::Mass, &[]),
+    Unit::new("mg", "milligram", Dimension::Mass, &[]),
+    Unit::new("t", "tonne", Dimension::Mass, &["ton"]),
+    Unit::new("lb", "pound", Dimension::Mass, &["lbs"]),
+    Unit::new("oz", "ounce", Dimension::Mass, &[]),
+    Unit::new("st", "stone", Dimension::Mass, &[]),
+    Unit::new("l", "liter", Dimension::Volume, &["litre"]),
+    Unit::new("ml", "milliliter", Dimension::Volume, &["millilitre"]),
+    Unit::new("m³", "cubic meter", Dimension::Volume, &["m3"]),
+    Unit::new("gal", "gallon", Dimension::Volume, &[]),
+    Unit::new("qt", "quart", Dimension::Volume, &[]),
+    Unit::new("pt", "pint", Dimension::Volume, &[]),
+    Unit::new("cup", "cup", Dimension::Volume, &[]),
+    Unit::new("floz", "fluid ounce", Dimension::Volume, &[]),
+    Unit::new("m/s", "meters per second", Dimension::Speed, &[]),
+    Unit::new(
+        "km/h",
+        "kilometers per hour",
+        Dimension::Speed,
+        &["kph", "kmh"],
+    ),
+    Unit::new("mph", "miles per hour", Dimension::Speed, &[]),
+    Unit::new("kn", "knot", Dimension::Speed, &[]),
+    Unit::new("ft/s", "feet per second", Dimension::Speed, &[]),
+    Unit::new("Pa", "pascal", Dimension::Pressure, &[]),
+    Unit::new("kPa", "kilopascal", Dimension::Pressure, &[]),
+    Unit::new("bar", "bar", Dimension::Pressure, &[]),
+    Unit::new("atm", "atmosphere", Dimension::Pressure, &[]),
+    Unit::new("psi", "pounds per square inch", Dimension::Pressure, &[]),
+    Unit::new("mmHg", "millimeter of mercury", Dimension::Pressure, &[]),
+    Unit::new("torr", "torr", Dimension::Pressure, &[]),
+    Unit::new("J", "joule", Dimension::Energy, &[]),
+    Unit::new("kJ", "kilojoule", Dimension::Energy, &[]),
+    Unit::new("cal", "calorie", Dimension::Energy, &[]),
+    Unit::new("kcal", "kilocalorie", Dimension::Energy, &["Cal"]),
+    Unit::new("Wh", "watt hour", Dimension::Energy, &[]),
+    Unit::new("kWh", "kilowatt hour", Dimension::Energy, &[]),
+    Unit::new("BTU", "british thermal unit", Dimension::Energy, &[]),
+    Unit::new("eV", "electronvolt", Dimension::Energy, &[]),
+    Unit::new("°C", "Celsius", Dimension::Temperature, &[]),
+    Unit::new("°F", "Fahrenheit", Dimension::Temperature, &[]),
+    // Kelvin is an absolute scale and is written without the degree sign
+    Unit::new("K", "Kelvin", Dimension::Temperature, &[]),
+    Unit::new("°Ra", "Rankine", Dimension::Temperature, &["R"]),
+    Unit::new("°Ré", "Réaumur", Dimension::Temperature, &["Re", "Reaumur"]),
+    Unit::new("°De", "Delisle", Dimension::Temperature, &["D"]),
+];
+
+// One row per known conversion: 1 `from` is `factor` times `to` plus `offset`.
+// Only enough rows to connect every unit of a dimension are listed, any other pair
+// is found by walking from unit to unit, e.g. mile -> yard -> foot -> inch -> centimeter.
+static CONVERSIONS: &[(&str, &str, f64, f64)] = &[
+    ("km", "m", 1000.0, 0.0),
+    ("m", "cm", 100.0, 0.0),
+    ("cm", "mm", 10.0, 0.0),
+    ("in", "cm", 2.54, 0.0),
+    ("ft", "in", 12.0, 0.0),
+    ("yd", "ft", 3.0, 0.0),
+    ("mi", "yd", 1760.0, 0.0),
+    ("nmi", "m", 1852.0, 0.0),
+    ("kg", "g", 1000.0, 0.0),
+    ("g", "mg", 1000.0, 0.0),
+    ("t", "kg", 1000.0, 0.0),
+    ("lb", "kg", 0.453_592_37, 0.0),
+    ("lb", "oz", 16.0, 0.0),
+    ("st", "lb", 14.0, 0.0),
+    ("m³", "l", 1000.0, 0.0),
+    ("l", "ml", 1000.0, 0.0),
+    ("gal", "l", 3.785_411_784, 0.0),
+    ("gal", "qt", 4.0, 0.0),
+    ("qt", "pt", 2.0, 0.0),
+    ("pt", "cup", 2.0, 0.0),
+    ("cup", "floz", 8.0, 0.0),
+    ("m/s", "km/h", 3.6, 0.0),
+    ("mph", "km/h", 1.609_344, 0.0),
+    ("kn", "km/h", 1.852, 0.0),
+    ("ft/s", "m/s", 0.3048, 0.0),
+    ("kPa", "Pa", 1000.0, 0.0),
+    ("bar", "kPa", 100.0, 0.0),
+    ("atm", "Pa", 101_325.0, 0.0),
+    ("atm", "torr", 760.0, 0.0),
+    ("psi", "Pa", 6_894.757_293_168, 0.0),
+    ("mmHg", "Pa", 133.322_387_415, 0.0),
+    ("kJ", "J", 1000.0, 0.0),
+    ("cal", "J", 4.184, 0.0),
+    ("kcal", "cal", 1000.0, 0.0),
+    ("Wh", "J", 3600.0, 0.0),
+    ("kWh", "Wh", 1000.0, 0.0),
+    ("BTU", "J", 1_055.055_852_62, 0.0),
+    ("eV", "J", 1.602_176_634e-19, 0.0),
+    // Celsius is the hub, so the everyday 100°C -> 212°F takes one exact step
+    ("°C", "°F", 1.8, 32.0),
+    ("°C", "K", 1.0, 273.15),
+    ("°C", "°Ré", 0.8, 0.0),
+    // Delisle counts down from the boiling point of water
+    ("°C", "°De", -1.5, 150.0),
+    ("K", "°Ra", 1.8, 0.0),
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum UnitError {
+    UnknownUnit(String),
+    // e.g. meters to kilograms
+    Dimension {
+        from: &'static Unit,
+        to: &'static Unit,
+    },
+    // both units have the same dimension but the table does not connect them
+    NoConversion {
+        from: &'static Unit,
+        to: &'static Unit,
+    },
+    NotANumber(String),
+    // a bare number where no default unit was given
+    MissingUnit(String),
+    Temperature(TemperatureError),
+}
+
+impl fmt::Display for UnitError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UnitError::UnknownUnit(name) => write!(f, "unknown unit `{name}`"),
+            UnitError::Dimension { from, to } => write!(
+                f,
+                "cannot convert {from} to {to}: {from} is a {} unit and {to} is a {} unit",
+                from.dimension, to.dimension
+            ),
+            UnitError::NoConversion { from, to } => {
+                write!(f, "no known way to convert {from} to {to}")
+            }
+            UnitError::NotANumber(text) => write!(f, "`{text}` is not a number"),
+            UnitError::MissingUnit(text) => {
+                write!(f, "`{text}` has no unit, write it like 98.6F")
+            }
+            UnitError::Temperature(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl std::error::Error for UnitError {}
+
+impl From<TemperatureError> for UnitError {
+    fn from(error: TemperatureError) -> UnitError {
+        UnitError::Temperature(error)
+    }
+}
+
+// Converts `value` from one unit to another by walking the conversion table breadth first,
+// so the path with the fewest steps (and the least rounding) is used.
+pub fn convert(value: f64, from: &'static Unit, to: &'static Unit) -> Result<f64, UnitError> {
+    if from.dimension != to.dimension {
+        return Err(UnitError::Dimension { from, to });
+    }
+
+    // every step is `value * factor + offset`, so a whole path folds into one such pair
+    let mut queue = VecDeque::from([(from.symbol, 1.0, 0.0)]);
+    let mut seen = vec![from.symbol];
+
+    while let Some((symbol, factor, offset)) = queue.pop_front() {
+        if symbol == to.symbol {
+            return Ok(value * factor + offset);
+        }
+
+        for &(a, b, step_factor, step_offset) in CONVERSIONS {
+            // rows can be walked backwards as well, `a = (b - offset) / factor`
+            let (next, step_factor, step_offset) = if a == symbol {
+                (b, step_factor, step_offset)
+            } else if b == symbol {
+                (a, 1.0 / step_factor, -step_offset / step_factor)
+            } else {
+                continue;
+            };
+
+            if !seen.contains(&next) {
+                seen.push(next);
+                queue.push_back((
+                    next,
+                    factor * step_factor,
+                    offset * step_factor + step_offset,
+                ));
+            }
+        }
+    }
+
+    Err(UnitError::NoConversion { from, to })
+}
+
+// A number with a unit. Temperatures are checked against absolute zero when they are made.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    pub value: f64,
+    pub unit: &'static Unit,
+}
+
+impl Quantity {
+    pub fn new(value: f64, unit: &'static Unit) -> Result<Quantity, UnitError> {
+        if let Some(scale) = unit.scale() {
+            Temperature::new(value, scale)?;
+        } else if !value.is_finite() {
+            return Err(UnitError::NotANumber(value.to_string()));
+        }
+
+        Ok(Quantity { value, unit })
+    }
+
+    // Reads a number directly followed by a unit, like "98.6F", "-40 °C", "1,000 m" or "5 km",
+    // with the number written the way `locale` writes them. A bare number uses `default` as its unit, when there is one.
+    pub fn parse(
+        text: &str,
+        default: Option<&'static Unit>,
+        locale: Locale,
+    ) -> Result<Quantity, UnitError> {
+        let text = text.trim();
+        // the number ends where the first character that cannot be part of it is
+        let split = text
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | ',' | '-' | '+')))
+            .unwrap_or(text.len());
+        let (number, unit) = text.split_at(split);
+
+        let value =
+            parse_number(number, locale).ok_or_else(|| UnitError::NotANumber(text.to_string()))?;
+
+        let unit = match (unit.trim(), default) {
+            ("", Some(default)) => default,
+            ("", None) => return Err(UnitError::MissingUnit(text.to_string())),
+            (unit, _) => {
+                Unit::find(unit).ok_or_else(|| UnitError::UnknownUnit(unit.to_string()))?
+            }
+        };
+
+        Quantity::new(value, unit)
+    }
+
+    pub fn to(&self, unit: &'static Unit) -> Result<Quantity, UnitError> {
+        Ok(Quantity {
+            value: convert(self.value, self.unit, unit)?,
+            unit,
+        })
+    }
+
+    // Where a quantity goes when no target unit is given: temperatures have their counterpart
+    // scale (°C <-> °F), for everything else there is no obvious choice.
+    pub fn counterpart(&self) -> Option<&'static Unit> {
+        self.unit.scale().map(|scale| scale.counterpart().unit())
+    }
+
+    pub fn format(&self, numbers: &NumberFormat) -> String {
+        let value = numbers.format(self.value);
+
+        match self.unit.dimension {
+            // temperatures are written the usual way, "37°C" and "310.15K"
+            Dimension::Temperature => format!("{value}{}", self.unit.symbol),
+            _ => format!("{value} {}", self.unit.symbol),
+        }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.format(&NumberFormat::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit(text: &str) -> &'static Unit {
+        Unit::find(text).unwrap()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * 1e-12,
+            "expected {expected}, got {actual}"
+        );
+    }
+
+    #[test]
+    fn units_are_found_by_symbol_name_and_plural() {
+        assert_eq!(unit("km").name, "kilometer");
+        assert_eq!(unit("Miles").symbol, "mi");
+        assert_eq!(unit("feet").symbol, "ft");
+        assert_eq!(unit("°c").symbol, "°C");
+        assert_eq!(unit("kelvins").symbol, "K");
+        assert!(Unit::find("furlong").is_none());
+    }
+
+    #[test]
+    fn symbols_are_not_guessed() {
+        assert_eq!(unit("cal").symbol, "cal");
+        assert_eq!(unit("Cal").symbol, "kcal");
+        assert_eq!(unit("calories").symbol, "cal");
+        // lowercase "cal" or the food "Cal", better to ask than to pick one
+        assert!(Unit::find("CAL").is_none());
+        assert!(Unit::find("cs").is_none());
+        assert!(Unit::find("ms").is_none());
+        assert_eq!(unit("KM/H").symbol, "km/h");
+    }
+
+    #[test]
+    fn conversions_walk_the_table() {
+        assert_close(convert(1.0, unit("mi"), unit("cm")).unwrap(), 160_934.4);
+        assert_close(convert(1.0, unit("mm"), unit("in")).unwrap(), 1.0 / 25.4);
+        assert_close(convert(1.0, unit("st"), unit("g")).unwrap(), 6_350.293_18);
+        assert_close(convert(1.0, unit("gal"), unit("floz")).unwrap(), 128.0);
+        assert_close(
+            convert(100.0, unit("km/h"), unit("m/s")).unwrap(),
+            27.777_777_777_777_78,
+        );
+        assert_close(
+            convert(1.0, unit("atm"), unit("psi")).unwrap(),
+            14.695_948_775_513_45,
+        );
+        assert_close(
+            convert(1.0, unit("kWh"), unit("kcal")).unwrap(),
+            860.420_650_095_602_3,
+        );
+    }
+
+    #[test]
+    fn temperatures_use_offsets() {
+        assert_close(convert(100.0, unit("C"), unit("F")).unwrap(), 212.0);
+        assert_close(convert(0.0, unit("K"), unit("F")).unwrap(), -459.67);
+        assert_close(convert(0.0, unit("De"), unit("K")).unwrap(), 373.15);
+        assert_close(convert(671.67, unit("Ra"), unit("Re")).unwrap(), 80.0);
+    }
+
+    #[test]
+    fn every_unit_reaches_every_unit_of_its_dimension() {
+        for from in UNITS {
+            for to in Unit::of(from.dimension) {
+                assert!(convert(1.0, from, to).is_ok(), "{from} -> {to}");
+            }
+        }
+    }
+
+    #[test]
+    fn different_dimensions_are_refused() {
+        let error = convert(1.0, unit("m"), unit("kg")).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "cannot convert m to kg: m is a length unit and kg is a mass unit"
+        );
+    }
+
+    #[test]
+    fn quantities_are_parsed() {
+        assert_eq!(
+            Quantity::parse("5 km", None, Locale::Point),
+            Ok(Quantity {
+                value: 5.0,
+                unit: unit("km")
+            })
+        );
+        assert_eq!(
+            Quantity::parse("300", Some(unit("K")), Locale::Point)
+                .unwrap()
+                .unit,
+            unit("K")
+        );
+        assert_eq!(
+            Quantity::parse("300", None, Locale::Point),
+            Err(UnitError::MissingUnit(String::from("300")))
+        );
+        assert_eq!(
+            Quantity::parse("36,6C", None, Locale::Comma).unwrap().value,
+            36.6
+        );
+        assert_eq!(
+            Quantity::parse("1,000 m", None, Locale::Point)
+                .unwrap()
+                .value,
+            1000.0
+        );
+        assert!(Quantity::parse("36,6C", None, Locale::Point).is_err());
+        assert!(Quantity::parse("-500F", None, Locale::Point).is_err());
+        assert!(Quantity::parse("3 parsecs", None, Locale::Point).is_err());
+    }
+}
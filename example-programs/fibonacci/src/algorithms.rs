@@ -0,0 +1,359 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::big::BigUint;
+
+// F(186) is the biggest Fibonacci number that fits in a u128, F(187) needs 129 bits.
+pub const MAX_U128_INDEX: u64 = 186;
+
+// F(n) is too big for a u128. Returned instead of a wrapped around, wrong number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Overflow {
+    pub n: u64,
+}
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "F({}) does not fit in 128 bits, the largest that does is F({MAX_U128_INDEX})",
+            self.n
+        )
+    }
+}
+
+impl std::error::Error for Overflow {}
+
+// Exact values get slow fast: F(2 000 000) has 417 975 digits and takes seconds, every doubling of n
+// takes about four times as long. Anything bigger is refused instead of keeping the program busy for minutes.
+pub const MAX_BIG_INDEX: u64 = 2_000_000;
+
+// F(n) is past `MAX_BIG_INDEX`, too big to compute exactly in reasonable time and memory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TooBig {
+    pub n: u64,
+}
+
+impl fmt::Display for TooBig {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "F({}) is too big to compute exactly, the largest allowed is F({MAX_BIG_INDEX})",
+            self.n
+        )
+    }
+}
+
+impl std::error::Error for TooBig {}
+
+// F(0) = 0, F(1) = 1 and every next one is the sum of the two before it.
+// This is the fastest of the algorithms, so it is the default.
+pub fn fibonacci(n: u64) -> Result<u128, Overflow> {
+    fast_doubling(n)
+}
+
+// Walks the sequence once, keeping only the last two numbers. O(n) additions.
+pub fn iterative(n: u64) -> Result<u128, Overflow> {
+    // `next` runs one step ahead, it is `None` once it no longer fits while `current` still may
+    let (mut current, mut next) = (0u128, Some(1u128));
+
+    for _ in 0..n {
+        let Some(value) = next else {
+            return Err(Overflow { n });
+        };
+
+        next = current.checked_add(value);
+        current = value;
+    }
+
+    Ok(current)
+}
+
+// The original recursion, F(n) = F(n - 1) + F(n - 2), but every number is computed only once
+// and remembered, so asking again later is free.
+#[derive(Debug, Clone)]
+pub struct Memo {
+    known: Vec<u128>,
+}
+
+impl Default for Memo {
+    fn default() -> Memo {
+        Memo { known: vec![0, 1] }
+    }
+}
+
+impl Memo {
+    pub fn new() -> Memo {
+        Memo::default()
+    }
+
+    pub fn get(&mut self, n: u64) -> Result<u128, Overflow> {
+        if n > MAX_U128_INDEX {
+            return Err(Overflow { n });
+        }
+
+        if let Some(known) = self.known.get(n as usize) {
+            return Ok(*known);
+        }
+
+        let value = self.get(n - 1)? + self.get(n - 2)?;
+        self.known.push(value);
+
+        Ok(value)
+    }
+}
+
+pub fn memoized(n: u64) -> Result<u128, Overflow> {
+    Memo::new().get(n)
+}
+
+// [[1, 1], [1, 0]] to the power of k is [[F(k + 1), F(k)], [F(k), F(k - 1)]].
+// Squaring halves the exponent, so only O(log n) matrix products are needed.
+pub fn matrix(n: u64) -> Result<u128, Overflow> {
+    type Matrix = [[u128; 2]; 2];
+
+    fn multiply(a: &Matrix, b: &Matrix) -> Option<Matrix> {
+        let cell = |row: usize, column: usize| {
+            a[row][0]
+                .checked_mul(b[0][column])?
+                .checked_add(a[row][1].checked_mul(b[1][column])?)
+        };
+
+        Some([[cell(0, 0)?, cell(0, 1)?], [cell(1, 0)?, cell(1, 1)?]])
+    }
+
+    if n == 0 {
+        return Ok(0);
+    }
+
+    // the power n - 1 has F(n) in its top left corner, the power n would need F(n + 1) which may not fit
+    let mut exponent = n - 1;
+    let mut result: Matrix = [[1, 0], [0, 1]];
+    let mut base: Matrix = [[1, 1], [1, 0]];
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply(&result, &base).ok_or(Overflow { n })?;
+        }
+
+        exponent >>= 1;
+        // squaring once more than needed could overflow for no reason
+        if exponent > 0 {
+            base = multiply(&base, &base).ok_or(Overflow { n })?;
+        }
+    }
+
+    Ok(result[0][0])
+}
+
+// Uses F(2k) = F(k) * (2 * F(k + 1) - F(k)) and F(2k + 1) = F(k)^2 + F(k + 1)^2
+// to go from F(k) to F(2k) in one step. O(log n) like the matrix, with fewer multiplications.
+pub fn fast_doubling(n: u64) -> Result<u128, Overflow> {
+    // (F(k), F(k + 1)), where F(k + 1) is `None` once it no longer fits
+    fn pair(k: u64) -> Option<(u128, Option<u128>)> {
+        if k == 0 {
+            return Some((0, Some(1)));
+        }
+
+        let (a, b) = pair(k / 2)?;
+        let b = b?;
+
+        let double = a.checked_mul(b.checked_mul(2)?.checked_sub(a)?);
+        let double_plus_one = a
+            .checked_mul(a)
+            .and_then(|square| square.checked_add(b.checked_mul(b)?));
+
+        if k.is_multiple_of(2) {
+            Some((double?, double_plus_one))
+        } else {
+            let odd = double_plus_one?;
+            Some((odd, double.and_then(|even| even.checked_add(odd))))
+        }
+    }
+
+    pair(n).map(|(value, _)| value).ok_or(Overflow { n })
+}
+
+// Exact F(n) up to `MAX_BIG_INDEX`, e.g. all 104 494 digits of F(500 000). Fast doubling again,
+// walking the bits of n from the highest one down instead of recursing.
+pub fn fibonacci_big(n: u64) -> Result<BigUint, TooBig> {
+    if n > MAX_BIG_INDEX {
+        return Err(TooBig { n });
+    }
+
+    // (F(k), F(k + 1)) where k is the bits of n seen so far
+    let (mut a, mut b) = (BigUint::zero(), BigUint::one());
+
+    for bit in (0..u64::BITS - n.leading_zeros()).rev() {
+        let double = &a * &(&(&b + &b) - &a);
+        let double_plus_one = &(&a * &a) + &(&b * &b);
+
+        (a, b) = match (n >> bit) & 1 {
+            1 => {
+                let next = &double + &double_plus_one;
+                (double_plus_one, next)
+            }
+            _ => (double, double_plus_one),
+        };
+    }
+
+    Ok(a)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Iterative,
+    Memoized,
+    Matrix,
+    FastDoubling,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 4] = [
+        Algorithm::Iterative,
+        Algorithm::Memoized,
+        Algorithm::Matrix,
+        Algorithm::FastDoubling,
+    ];
+
+    pub fn compute(&self, n: u64) -> Result<u128, Overflow> {
+        match self {
+            Algorithm::Iterative => iterative(n),
+            Algorithm::Memoized => memoized(n),
+            Algorithm::Matrix => matrix(n),
+            Algorithm::FastDoubling => fast_doubling(n),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Algorithm::Iterative => "iterative",
+            Algorithm::Memoized => "memoized",
+            Algorithm::Matrix => "matrix",
+            Algorithm::FastDoubling => "fast-doubling",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Algorithm, String> {
+        match name.trim().to_lowercase().as_str() {
+            "iterative" => Ok(Algorithm::Iterative),
+            "memoized" | "memo" => Ok(Algorithm::Memoized),
+            "matrix" => Ok(Algorithm::Matrix),
+            "fast-doubling" | "doubling" => Ok(Algorithm::FastDoubling),
+            _ => Err(format!(
+                "unknown algorithm `{name}`, expected iterative, memoized, matrix or fast-doubling"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const F186: u128 = 332_825_110_087_067_562_321_196_029_789_634_457_848;
+
+    #[test]
+    fn known_values() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(algorithm.compute(0), Ok(0), "{algorithm}");
+            assert_eq!(algorithm.compute(1), Ok(1), "{algorithm}");
+            assert_eq!(algorithm.compute(2), Ok(1), "{algorithm}");
+            assert_eq!(algorithm.compute(10), Ok(55), "{algorithm}");
+            assert_eq!(algorithm.compute(48), Ok(4_807_526_976), "{algorithm}");
+            assert_eq!(
+                algorithm.compute(93),
+                Ok(12_200_160_415_121_876_738),
+                "{algorithm}"
+            );
+            assert_eq!(algorithm.compute(MAX_U128_INDEX), Ok(F186), "{algorithm}");
+        }
+    }
+
+    #[test]
+    fn all_algorithms_agree() {
+        for n in 0..=MAX_U128_INDEX {
+            let expected = iterative(n);
+
+            for algorithm in Algorithm::ALL {
+                assert_eq!(algorithm.compute(n), expected, "{algorithm} F({n})");
+            }
+        }
+    }
+
+    #[test]
+    fn overflow_is_reported() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(
+                algorithm.compute(MAX_U128_INDEX + 1),
+                Err(Overflow { n: 187 }),
+                "{algorithm}"
+            );
+            assert_eq!(
+                algorithm.compute(10_000),
+                Err(Overflow { n: 10_000 }),
+                "{algorithm}"
+            );
+        }
+    }
+
+    #[test]
+    fn memo_remembers() {
+        let mut memo = Memo::new();
+
+        assert_eq!(memo.get(100), Ok(354_224_848_179_261_915_075));
+        assert_eq!(memo.known.len(), 101);
+        assert_eq!(memo.get(50), Ok(12_586_269_025));
+    }
+
+    #[test]
+    fn big_matches_u128() {
+        for n in 0..=MAX_U128_INDEX {
+            assert_eq!(
+                fibonacci_big(n).unwrap(),
+                BigUint::from(fibonacci(n).unwrap()),
+                "F({n})"
+            );
+        }
+    }
+
+    #[test]
+    fn big_past_u128() {
+        let digits = fibonacci_big(1000).unwrap().to_string();
+
+        assert_eq!(digits.len(), 209);
+        assert!(digits.starts_with("43466557686937456435"));
+        assert!(digits.ends_with("76137795166849228875"));
+        assert_eq!(
+            fibonacci_big(100_000).unwrap().abbreviated(10),
+            "2597406934...3428746875 (20899 digits)"
+        );
+    }
+
+    #[test]
+    fn big_has_a_limit() {
+        assert_eq!(
+            fibonacci_big(MAX_BIG_INDEX + 1),
+            Err(TooBig {
+                n: MAX_BIG_INDEX + 1
+            })
+        );
+        assert!(fibonacci_big(u64::MAX).is_err());
+    }
+
+    #[test]
+    fn algorithm_names() {
+        for algorithm in Algorithm::ALL {
+            assert_eq!(algorithm.to_string().parse(), Ok(algorithm));
+        }
+        assert!("guess".parse::<Algorithm>().is_err());
+    }
+}
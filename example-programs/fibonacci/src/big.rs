@@ -0,0 +1,290 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Sub};
+
+// Printing works in chunks of nine decimal digits, the most that fit in a u32 limb.
+const DECIMAL_CHUNK: u32 = 1_000_000_000;
+const DECIMAL_CHUNK_DIGITS: usize = 9;
+
+// An unsigned integer of any size, for Fibonacci numbers far past what a u128 holds.
+// Stored as base 2^32 digits ("limbs"), lowest first, without zero limbs at the end,
+// so zero is an empty vector and two equal numbers always have equal limbs.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    limbs: Vec<u32>,
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint::default()
+    }
+
+    pub fn one() -> BigUint {
+        BigUint::from(1u32)
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // How many decimal digits the number has, 1 for zero.
+    pub fn digit_count(&self) -> usize {
+        self.to_string().len()
+    }
+
+    // "F(n) = 12345...67890 (104494 digits)" is more useful than 100 000 digits scrolling by.
+    // Numbers short enough to show whole are not cut.
+    pub fn abbreviated(&self, keep: usize) -> String {
+        let digits = self.to_string();
+
+        if digits.len() <= 2 * keep {
+            return digits;
+        }
+
+        format!(
+            "{}...{} ({} digits)",
+            &digits[..keep],
+            &digits[digits.len() - keep..],
+            digits.len()
+        )
+    }
+
+    fn from_limbs(mut limbs: Vec<u32>) -> BigUint {
+        while limbs.last() == Some(&0) {
+            limbs.pop();
+        }
+
+        BigUint { limbs }
+    }
+
+    // Divides in place and returns the remainder, long division from the highest limb down.
+    fn divide_small(&mut self, divisor: u32) -> u32 {
+        let mut remainder = 0u64;
+
+        for limb in self.limbs.iter_mut().rev() {
+            let current = (remainder << 32) | u64::from(*limb);
+            *limb = (current / u64::from(divisor)) as u32;
+            remainder = current % u64::from(divisor);
+        }
+
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+
+        remainder as u32
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(value: u32) -> BigUint {
+        BigUint::from(u128::from(value))
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        BigUint::from(u128::from(value))
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(mut value: u128) -> BigUint {
+        let mut limbs = Vec::new();
+
+        while value > 0 {
+            limbs.push(value as u32);
+            value >>= 32;
+        }
+
+        BigUint { limbs }
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        // no zero limbs at the end, so more limbs is always a bigger number
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Add for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = match self.limbs.len() >= other.limbs.len() {
+            true => (&self.limbs, &other.limbs),
+            false => (&other.limbs, &self.limbs),
+        };
+
+        let mut limbs = Vec::with_capacity(long.len() + 1);
+        let mut carry = 0u64;
+
+        for (index, limb) in long.iter().enumerate() {
+            let sum = u64::from(*limb) + u64::from(short.get(index).copied().unwrap_or(0)) + carry;
+            limbs.push(sum as u32);
+            carry = sum >> 32;
+        }
+
+        if carry > 0 {
+            limbs.push(carry as u32);
+        }
+
+        BigUint { limbs }
+    }
+}
+
+// Like the built in unsigned types, going below zero panics.
+impl Sub for &BigUint {
+    type Output = BigUint;
+
+    fn sub(self, other: &BigUint) -> BigUint {
+        assert!(self >= other, "attempt to subtract with overflow");
+
+        let mut limbs = Vec::with_capacity(self.limbs.len());
+        let mut borrow = 0i64;
+
+        for (index, limb) in self.limbs.iter().enumerate() {
+            let mut difference =
+                i64::from(*limb) - i64::from(other.limbs.get(index).copied().unwrap_or(0)) - borrow;
+
+            borrow = match difference < 0 {
+                true => {
+                    difference += 1 << 32;
+                    1
+                }
+                false => 0,
+            };
+            limbs.push(difference as u32);
+        }
+
+        BigUint::from_limbs(limbs)
+    }
+}
+
+// Schoolbook multiplication, every limb times every limb. Quadratic, but fast enough for
+// numbers with a few hundred thousand digits.
+impl Mul for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+
+        let mut limbs = vec![0u32; self.limbs.len() + other.limbs.len()];
+
+        for (i, a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u64;
+
+            for (j, b) in other.limbs.iter().enumerate() {
+                // u32::MAX * u32::MAX + 2 * u32::MAX still fits in a u64
+                let product = u64::from(*a) * u64::from(*b) + u64::from(limbs[i + j]) + carry;
+                limbs[i + j] = product as u32;
+                carry = product >> 32;
+            }
+
+            limbs[i + other.limbs.len()] = carry as u32;
+        }
+
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_zero() {
+            return write!(f, "0");
+        }
+
+        // nine digit chunks, lowest first
+        let mut rest = self.clone();
+        let mut chunks = Vec::new();
+        while !rest.is_zero() {
+            chunks.push(rest.divide_small(DECIMAL_CHUNK));
+        }
+
+        let mut chunks = chunks.iter().rev();
+        if let Some(first) = chunks.next() {
+            write!(f, "{first}")?;
+        }
+        for chunk in chunks {
+            write!(f, "{chunk:0width$}", width = DECIMAL_CHUNK_DIGITS)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(value: u128) -> BigUint {
+        BigUint::from(value)
+    }
+
+    #[test]
+    fn arithmetic_matches_u128() {
+        let values = [0, 1, 7, u32::MAX as u128, u64::MAX as u128, 1 << 100];
+
+        for a in values {
+            for b in values {
+                assert_eq!(&big(a) + &big(b), big(a + b), "{a} + {b}");
+                if a >= b {
+                    assert_eq!(&big(a) - &big(b), big(a - b), "{a} - {b}");
+                }
+                if let Some(product) = a.checked_mul(b) {
+                    assert_eq!(&big(a) * &big(b), big(product), "{a} * {b}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn products_past_u128() {
+        let square = &big(u128::MAX) * &big(u128::MAX);
+        let expected = &(&big(u128::MAX) * &big(u128::MAX - 1)) + &big(u128::MAX);
+
+        assert_eq!(square, expected);
+        assert_eq!(
+            (&big(1 << 100) * &big(3u128.pow(70))).to_string(),
+            "3173126578369279394610431020106175019306389315838160670214324224"
+        );
+    }
+
+    #[test]
+    fn decimal_printing() {
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(big(1_000_000_000).to_string(), "1000000000");
+        assert_eq!(big(u128::MAX).to_string(), u128::MAX.to_string());
+        assert_eq!(big(u128::MAX).digit_count(), 39);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(big(1 << 64) > big(u64::MAX as u128));
+        assert!(big(2) < big(3));
+        assert_eq!(big(5).cmp(&big(5)), Ordering::Equal);
+    }
+
+    #[test]
+    #[should_panic(expected = "subtract with overflow")]
+    fn subtracting_below_zero_panics() {
+        let _ = &big(1) - &big(2);
+    }
+
+    #[test]
+    fn abbreviation() {
+        assert_eq!(big(12345).abbreviated(3), "12345");
+        assert_eq!(big(u128::MAX).abbreviated(5), "34028...11455 (39 digits)");
+    }
+}
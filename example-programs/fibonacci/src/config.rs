@@ -0,0 +1,364 @@
+use std::path::PathBuf;
+
+use crate::algorithms::Algorithm;
+use crate::modular::MAX_PISANO_MODULUS;
+use crate::sequence::{LinearRecurrence, Sequence};
+
+// How the result is computed and printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Output {
+    // a u128 from the chosen algorithm, an error past F(186)
+    Fixed,
+    // `--big`: every digit, up to F(MAX_BIG_INDEX)
+    Full,
+    // `--digits K`: the digit count with the first and last K digits
+    Abbreviated(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    // whitespace separated numbers in, Fibonacci coded bytes out
+    Encode,
+    Decode,
+}
+
+// `--encode numbers.txt --output numbers.fib`, stdout when there is no `--output`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Coding {
+    pub direction: Direction,
+    pub input: PathBuf,
+    pub output: Option<PathBuf>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    // `--algorithm iterative`, fast doubling when it is not given
+    pub algorithm: Algorithm,
+    pub output: Output,
+    // `--sequence pell` or `--coefficients 1,1,1 --seeds 0,0,1`, `None` is plain Fibonacci
+    pub sequence: Option<LinearRecurrence>,
+    // `--list`: every term from 0 up to n instead of only term n
+    pub list: bool,
+    // `--mod M`: F(n) mod M, n can be as big as a u64 gets
+    pub modulus: Option<u64>,
+    // `--pisano M`: the Pisano period of M, nothing is read from stdin. M is at most 10^12,
+    // see `MAX_PISANO_MODULUS`
+    pub pisano: Option<u64>,
+    // `--zeckendorf`: n as a sum of Fibonacci numbers instead of F(n)
+    pub zeckendorf: bool,
+    pub coding: Option<Coding>,
+    // `--input queries.txt`: one query per line, stdin when there is no file
+    pub input: Option<PathBuf>,
+}
+
+impl Config {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+
+        let mut algorithm = None;
+        let mut output = Output::Fixed;
+        let mut sequence = None;
+        let mut coefficients = None;
+        let mut seeds = None;
+        let mut list = false;
+        let mut modulus = None;
+        let mut pisano = None;
+        let mut zeckendorf = false;
+        let mut coding = None;
+        let mut output_file = None;
+        let mut input = None;
+        // options that do something else than computing F(n), only one of them at a time
+        let mut standalone = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--algorithm" => algorithm = Some(value_of(&arg, args.next())?.parse()?),
+                "--big" => output = Output::Full,
+                "--digits" => {
+                    let value = value_of(&arg, args.next())?;
+                    output = match value.parse() {
+                        Ok(keep) if keep > 0 => Output::Abbreviated(keep),
+                        _ => return Err(format!("`{value}` is not a valid value for `{arg}`")),
+                    };
+                }
+                "--sequence" => sequence = Some(value_of(&arg, args.next())?.parse::<Sequence>()?),
+                "--coefficients" => coefficients = Some(numbers_of(&arg, args.next())?),
+                "--seeds" => seeds = Some(numbers_of(&arg, args.next())?),
+                "--list" => list = true,
+                "--mod" => modulus = Some(modulus_of(&arg, args.next())?),
+                "--pisano" => {
+                    let modulus = modulus_of(&arg, args.next())?;
+                    if modulus > MAX_PISANO_MODULUS {
+                        return Err(format!(
+                            "`--pisano` takes moduli up to {MAX_PISANO_MODULUS}, bigger ones are too slow to factor"
+                        ));
+                    }
+                    pisano = Some(modulus);
+                }
+                "--zeckendorf" => zeckendorf = true,
+                "--encode" | "--decode" => {
+                    let input = PathBuf::from(value_of(&arg, args.next())?);
+                    let direction = match arg.as_str() {
+                        "--encode" => Direction::Encode,
+                        _ => Direction::Decode,
+                    };
+                    coding = Some((direction, input));
+                }
+                "--output" => output_file = Some(PathBuf::from(value_of(&arg, args.next())?)),
+                "--input" => input = Some(PathBuf::from(value_of(&arg, args.next())?)),
+                other => return Err(format!("unknown argument `{other}`")),
+            }
+
+            if ["--mod", "--pisano", "--zeckendorf", "--encode", "--decode"].contains(&arg.as_str())
+            {
+                standalone.push(arg);
+            }
+        }
+
+        let sequence = match (sequence, coefficients, seeds) {
+            (Some(_), Some(_), _) | (Some(_), _, Some(_)) => {
+                return Err(String::from(
+                    "`--sequence` cannot be used with `--coefficients` or `--seeds`",
+                ))
+            }
+            // the algorithms are faster for plain Fibonacci
+            (Some(Sequence::Fibonacci), None, None) | (None, None, None) => None,
+            (Some(sequence), None, None) => Some(sequence.recurrence()),
+            (None, Some(coefficients), Some(seeds)) => {
+                Some(LinearRecurrence::new(coefficients, seeds).map_err(|err| err.to_string())?)
+            }
+            (None, _, _) => {
+                return Err(String::from(
+                    "`--coefficients` and `--seeds` must be given together",
+                ))
+            }
+        };
+
+        if sequence.is_some() && (algorithm.is_some() || output != Output::Fixed) {
+            return Err(String::from(
+                "`--algorithm`, `--big` and `--digits` only work for Fibonacci itself",
+            ));
+        }
+
+        if list && output != Output::Fixed {
+            return Err(String::from(
+                "`--list` only prints numbers that fit in 128 bits, it cannot be used with `--big` or `--digits`",
+            ));
+        }
+
+        let plain = sequence.is_none() && algorithm.is_none() && output == Output::Fixed && !list;
+        if let Some(flag) = standalone.first() {
+            if standalone.len() > 1 || !plain {
+                return Err(format!("`{flag}` cannot be combined with other options"));
+            }
+        }
+
+        // neither reads queries
+        if input.is_some() && (pisano.is_some() || coding.is_some()) {
+            return Err(String::from(
+                "`--input` cannot be used with `--pisano`, `--encode` or `--decode`",
+            ));
+        }
+
+        let coding = match (coding, output_file) {
+            (Some((direction, input)), output) => Some(Coding {
+                direction,
+                input,
+                output,
+            }),
+            (None, Some(_)) => {
+                return Err(String::from(
+                    "`--output` only works with `--encode` or `--decode`",
+                ))
+            }
+            (None, None) => None,
+        };
+
+        // big numbers always use fast doubling, the others would take far too long
+        if let (Some(algorithm), Output::Full | Output::Abbreviated(_)) = (algorithm, output) {
+            if algorithm != Algorithm::FastDoubling {
+                return Err(format!(
+                    "`--algorithm {algorithm}` cannot be used with big numbers, they always use fast-doubling"
+                ));
+            }
+        }
+
+        Ok(Config {
+            algorithm: algorithm.unwrap_or(Algorithm::FastDoubling),
+            output,
+            sequence,
+            list,
+            modulus,
+            pisano,
+            zeckendorf,
+            coding,
+            input,
+        })
+    }
+}
+
+fn value_of(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("`{flag}` needs a value"))
+}
+
+fn modulus_of(flag: &str, value: Option<String>) -> Result<u64, String> {
+    let value = value_of(flag, value)?;
+
+    match value.parse() {
+        Ok(modulus) if modulus > 0 => Ok(modulus),
+        _ => Err(format!(
+            "`{value}` is not a valid value for `{flag}`, it must be a whole number of at least 1"
+        )),
+    }
+}
+
+// "1,1,1" or "2, -1"
+fn numbers_of(flag: &str, value: Option<String>) -> Result<Vec<i128>, String> {
+    let value = value_of(flag, value)?;
+
+    value
+        .split(',')
+        .map(|number| {
+            number.trim().parse().map_err(|_| {
+                format!(
+                    "`{}` in `{flag} {value}` is not a whole number",
+                    number.trim()
+                )
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Config, String> {
+        let args = std::iter::once("fibonacci")
+            .chain(args.iter().copied())
+            .map(String::from);
+
+        Config::build(args)
+    }
+
+    #[test]
+    fn algorithm_flag() {
+        assert_eq!(build(&[]).unwrap().algorithm, Algorithm::FastDoubling);
+        assert_eq!(
+            build(&["--algorithm", "matrix"]).unwrap().algorithm,
+            Algorithm::Matrix
+        );
+        assert!(build(&["--algorithm"]).is_err());
+        assert!(build(&["--algorithm", "guess"]).is_err());
+        assert!(build(&["--fast"]).is_err());
+    }
+
+    #[test]
+    fn big_output() {
+        assert_eq!(build(&[]).unwrap().output, Output::Fixed);
+        assert_eq!(build(&["--big"]).unwrap().output, Output::Full);
+        assert_eq!(
+            build(&["--digits", "20"]).unwrap().output,
+            Output::Abbreviated(20)
+        );
+        assert!(build(&["--big", "--algorithm", "fast-doubling"]).is_ok());
+        assert!(build(&["--big", "--algorithm", "memoized"]).is_err());
+        assert!(build(&["--digits", "0"]).is_err());
+        assert!(build(&["--digits"]).is_err());
+    }
+
+    #[test]
+    fn sequences() {
+        assert_eq!(build(&[]).unwrap().sequence, None);
+        assert_eq!(build(&["--sequence", "fibonacci"]).unwrap().sequence, None);
+        assert_eq!(
+            build(&["--sequence", "pell", "--list"]).unwrap(),
+            Config {
+                algorithm: Algorithm::FastDoubling,
+                output: Output::Fixed,
+                sequence: Some(Sequence::Pell.recurrence()),
+                list: true,
+                modulus: None,
+                pisano: None,
+                zeckendorf: false,
+                coding: None,
+                input: None
+            }
+        );
+
+        let custom = build(&["--coefficients", "1, 1, 1", "--seeds", "0,0,1"])
+            .unwrap()
+            .sequence;
+        assert_eq!(custom, Some(Sequence::Tribonacci.recurrence()));
+
+        assert!(build(&["--sequence", "primes"]).is_err());
+        assert!(build(&["--coefficients", "1,1"]).is_err());
+        assert!(build(&["--coefficients", "1,1", "--seeds", "1"]).is_err());
+        assert!(build(&["--coefficients", "1,x", "--seeds", "1,1"]).is_err());
+        assert!(build(&["--sequence", "lucas", "--seeds", "1,1"]).is_err());
+        assert!(build(&["--sequence", "lucas", "--big"]).is_err());
+        assert!(build(&["--list", "--big"]).is_err());
+    }
+
+    #[test]
+    fn modular() {
+        assert_eq!(build(&["--mod", "10"]).unwrap().modulus, Some(10));
+        assert_eq!(build(&["--pisano", "10"]).unwrap().pisano, Some(10));
+        assert!(build(&["--mod", "0"]).is_err());
+        assert!(build(&["--mod", "-3"]).is_err());
+        assert!(build(&["--pisano"]).is_err());
+        assert_eq!(
+            build(&["--pisano", "1000000000000"]).unwrap().pisano,
+            Some(MAX_PISANO_MODULUS)
+        );
+        assert!(build(&["--pisano", "1000000000001"]).is_err());
+        // 10^12 is fine for `--mod`, it never factors
+        assert!(build(&["--mod", "1000000000001"]).is_ok());
+        assert!(build(&["--mod", "10", "--big"]).is_err());
+        assert!(build(&["--mod", "10", "--pisano", "10"]).is_err());
+        assert!(build(&["--pisano", "10", "--list"]).is_err());
+    }
+
+    #[test]
+    fn fibonacci_coding() {
+        assert!(build(&["--zeckendorf"]).unwrap().zeckendorf);
+        assert_eq!(
+            build(&["--encode", "numbers.txt", "--output", "numbers.fib"])
+                .unwrap()
+                .coding,
+            Some(Coding {
+                direction: Direction::Encode,
+                input: PathBuf::from("numbers.txt"),
+                output: Some(PathBuf::from("numbers.fib"))
+            })
+        );
+        assert_eq!(
+            build(&["--decode", "numbers.fib"]).unwrap().coding,
+            Some(Coding {
+                direction: Direction::Decode,
+                input: PathBuf::from("numbers.fib"),
+                output: None
+            })
+        );
+
+        assert!(build(&["--encode"]).is_err());
+        assert!(build(&["--output", "out.fib"]).is_err());
+        assert!(build(&["--encode", "a", "--decode", "b"]).is_err());
+        assert!(build(&["--zeckendorf", "--mod", "3"]).is_err());
+        assert!(build(&["--decode", "numbers.fib", "--list"]).is_err());
+    }
+
+    #[test]
+    fn query_file() {
+        assert_eq!(build(&[]).unwrap().input, None);
+        assert_eq!(
+            build(&["--input", "queries.txt", "--mod", "10"])
+                .unwrap()
+                .input,
+            Some(PathBuf::from("queries.txt"))
+        );
+        assert!(build(&["--input"]).is_err());
+        assert!(build(&["--input", "queries.txt", "--pisano", "10"]).is_err());
+        assert!(build(&["--input", "queries.txt", "--decode", "numbers.fib"]).is_err());
+    }
+}
@@ -0,0 +1,18 @@
+// The algorithms live in this library crate so they can be tested and compared, `main.rs` only reads input.
+pub mod algorithms;
+pub mod big;
+pub mod config;
+pub mod modular;
+pub mod query;
+pub mod sequence;
+pub mod zeckendorf;
+
+pub use algorithms::{
+    fibonacci, fibonacci_big, Algorithm, Memo, Overflow, TooBig, MAX_BIG_INDEX, MAX_U128_INDEX,
+};
+pub use big::BigUint;
+pub use config::{Coding, Config, Direction, Output};
+pub use modular::{fibonacci_mod, pisano_period, ZeroModulus, MAX_PISANO_MODULUS};
+pub use query::{Query, QueryError, QueryErrorKind, MAX_LIST_TERMS};
+pub use sequence::{Fibonacci, LinearRecurrence, RecurrenceError, Sequence, Terms};
+pub use zeckendorf::{decode, encode, zeckendorf, DecodeError};
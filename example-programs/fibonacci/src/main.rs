@@ -1,21 +1,234 @@
-use std::io::stdin;
+use std::env;
+use std::error::Error;
+use std::fs::{self, File};
+use std::io::{stdin, stdout, BufRead, BufReader, IsTerminal, Write};
+use std::process;
+
+use fibonacci::{
+    decode, encode, fibonacci_big, fibonacci_mod, pisano_period, zeckendorf, Coding, Config,
+    Direction, Fibonacci, Output, Query, MAX_LIST_TERMS,
+};
 
 fn main() {
-    let mut input = String::new();
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    if let Some(coding) = &config.coding {
+        if let Err(err) = code(coding) {
+            eprintln!("Application error: {err}");
+            process::exit(1);
+        }
+        return;
+    }
+
+    // the only question that does not need an n
+    if let Some(modulus) = config.pisano {
+        // `Config::build` only lets moduli of at least 1 through
+        println!("Result: {}", pisano_period(modulus).unwrap());
+        return;
+    }
+
+    let input: Box<dyn BufRead> = match &config.input {
+        Some(path) => match File::open(path) {
+            Ok(file) => Box::new(BufReader::new(file)),
+            Err(error) => {
+                eprintln!("Could not open {}: {error}", path.display());
+                process::exit(1);
+            }
+        },
+        None => Box::new(stdin().lock()),
+    };
+    // a prompt only makes sense when someone is typing
+    let interactive = config.input.is_none() && stdin().is_terminal();
+
+    if !session(&config, input, interactive) {
+        process::exit(1);
+    }
+}
+
+// Answers queries line by line until the input ends or says `quit`.
+// A bad line is reported and skipped, the return value says whether every line was fine.
+fn session(config: &Config, mut input: impl BufRead, interactive: bool) -> bool {
+    let mut all_fine = true;
+    let mut number = 0;
+
+    if interactive {
+        println!("Enter n, a range like 10..20 or a list like 1, 5, 8. Type quit to stop.");
+    }
+
+    loop {
+        if interactive {
+            print!("> ");
+            // the prompt has no newline, without a flush it would show up after the answer
+            stdout().flush().unwrap_or(());
+        }
+
+        let mut line = String::new();
+        match input.read_line(&mut line) {
+            Ok(0) => break,
+            Ok(_) => number += 1,
+            Err(error) => {
+                eprintln!("There was an error with your input: {error}");
+                return false;
+            }
+        }
+
+        let line = line.trim_end_matches(['\n', '\r']);
+        if matches!(line.trim(), "quit" | "exit" | "q") {
+            break;
+        }
+
+        let query = match Query::parse(line) {
+            Ok(query) => query,
+            Err(err) => {
+                match interactive {
+                    true => eprintln!("{err}\n{}", err.pointer(line)),
+                    false => eprintln!("line {number}: {err}\n{}", err.pointer(line)),
+                }
+                all_fine = false;
+                continue;
+            }
+        };
+
+        for n in query.numbers() {
+            match (answer(config, n), query.is_single()) {
+                (Ok(answer), true) => println!("Result: {answer}"),
+                (Ok(answer), false) => println!("{n}: {answer}"),
+                (Err(err), true) => {
+                    eprintln!("Problem computing the number: {err}");
+                    all_fine = false;
+                }
+                (Err(err), false) => {
+                    eprintln!("{n}: {err}");
+                    all_fine = false;
+                }
+            }
+        }
+    }
+
+    all_fine
+}
+
+// What the options ask for about n, as the text that is printed for it.
+fn answer(config: &Config, n: u64) -> Result<String, String> {
+    if let Some(modulus) = config.modulus {
+        return fibonacci_mod(n, modulus)
+            .map(|value| value.to_string())
+            .map_err(|err| err.to_string());
+    }
+
+    if config.zeckendorf {
+        let parts: Vec<String> = zeckendorf(u128::from(n))
+            .iter()
+            .map(|part| part.to_string())
+            .collect();
+
+        return Ok(match parts.is_empty() {
+            true => String::from("0 is the empty sum"),
+            false => format!("{n} = {}", parts.join(" + ")),
+        });
+    }
 
-    stdin().read_line(&mut input).expect("There was an error with your imput.");
+    if config.list {
+        if n >= MAX_LIST_TERMS {
+            return Err(format!(
+                "--list prints at most {MAX_LIST_TERMS} terms, ask for n below that"
+            ));
+        }
 
-    let input: u32 = input.trim().parse().expect("Invalid number");
+        return Ok(list(config, n));
+    }
 
-    let final_result = fibonacci(input);
+    if let Some(recurrence) = &config.sequence {
+        return recurrence
+            .term(n)
+            .map(|term| term.to_string())
+            .ok_or_else(|| {
+                format!("term {n}, or a number needed for it, does not fit in 128 bits")
+            });
+    }
 
-    println!("Result: {final_result}")
+    match config.output {
+        Output::Fixed => config
+            .algorithm
+            .compute(n)
+            .map(|value| value.to_string())
+            .map_err(|err| format!("{err}, use --big for the exact value")),
+        Output::Full => fibonacci_big(n)
+            .map(|value| value.to_string())
+            .map_err(|err| err.to_string()),
+        Output::Abbreviated(keep) => fibonacci_big(n)
+            .map(|value| value.abbreviated(keep))
+            .map_err(|err| err.to_string()),
+    }
 }
 
-fn fibonacci(num: u32) -> u32 {
-    match num {
-        0 => return 0,
-        1 | 2 => return 1,
-        x => return fibonacci(x - 1) + fibonacci(x - 2) 
+// Terms 0 to `last`, separated by commas.
+fn list(config: &Config, last: u64) -> String {
+    let count = (last as usize).saturating_add(1);
+
+    let terms: Vec<String> = match &config.sequence {
+        Some(recurrence) => recurrence
+            .terms()
+            .take(count)
+            .map(|term| term.to_string())
+            .collect(),
+        None => Fibonacci::new()
+            .take(count)
+            .map(|term| term.to_string())
+            .collect(),
+    };
+
+    match terms.len() < count {
+        true => format!(
+            "{}, and no more, term {} does not fit in 128 bits",
+            terms.join(", "),
+            terms.len()
+        ),
+        false => terms.join(", "),
     }
 }
+
+// Encodes a file of whitespace separated numbers into Fibonacci code, or decodes one back
+// into a number per line.
+fn code(coding: &Coding) -> Result<(), Box<dyn Error>> {
+    let output = match coding.direction {
+        Direction::Encode => {
+            let text = fs::read_to_string(&coding.input)?;
+            let mut values = Vec::new();
+
+            for (index, line) in text.lines().enumerate() {
+                for token in line.split_whitespace() {
+                    let value: u64 = token.parse().map_err(|_| {
+                        format!(
+                            "line {}: `{token}` is not a whole number from 0 to {}",
+                            index + 1,
+                            u64::MAX
+                        )
+                    })?;
+                    values.push(value);
+                }
+            }
+
+            encode(&values)
+        }
+        Direction::Decode => {
+            let values = decode(&fs::read(&coding.input)?)?;
+
+            values
+                .iter()
+                .map(|value| format!("{value}\n"))
+                .collect::<String>()
+                .into_bytes()
+        }
+    };
+
+    match &coding.output {
+        Some(path) => fs::write(path, output)?,
+        None => stdout().write_all(&output)?,
+    }
+
+    Ok(())
+}
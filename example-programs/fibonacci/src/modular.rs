@@ -0,0 +1,201 @@
+use std::fmt;
+
+// Everything modulo 0 is a division by zero.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ZeroModulus;
+
+impl fmt::Display for ZeroModulus {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "the modulus must be at least 1")
+    }
+}
+
+impl std::error::Error for ZeroModulus {}
+
+// Trial division tries factors up to √m, a million of them for 10^12 but billions for a prime
+// near u64::MAX, so `--pisano` refuses bigger moduli.
+pub const MAX_PISANO_MODULUS: u64 = 1_000_000_000_000;
+
+// F(n) mod m without ever computing F(n), so n = 10^18 takes about 60 steps.
+// Fast doubling like `algorithms::fast_doubling`, every product reduced right away.
+pub fn fibonacci_mod(n: u64, m: u64) -> Result<u64, ZeroModulus> {
+    if m == 0 {
+        return Err(ZeroModulus);
+    }
+
+    Ok(pair_mod(u128::from(n), m).0)
+}
+
+// (F(n) mod m, F(n + 1) mod m). n is a u128 because Pisano periods can be up to 6m.
+fn pair_mod(n: u128, m: u64) -> (u64, u64) {
+    let m = u128::from(m);
+    // (F(k), F(k + 1)) where k is the bits of n seen so far, both below m so products fit in a u128
+    let (mut a, mut b) = (0, 1 % m);
+
+    for bit in (0..u128::BITS - n.leading_zeros()).rev() {
+        let double = a * ((2 * b + m - a) % m) % m;
+        let double_plus_one = (a * a % m + b * b % m) % m;
+
+        (a, b) = match (n >> bit) & 1 {
+            1 => (double_plus_one, (double + double_plus_one) % m),
+            _ => (double, double_plus_one),
+        };
+    }
+
+    (a as u64, b as u64)
+}
+
+// The Pisano period π(m): Fibonacci numbers mod m repeat, and this is the length of one round.
+// π(10) = 60, so the last digit of F(n) is the last digit of F(n mod 60).
+//
+// Walking the sequence until it starts over would take up to 6m steps, instead it uses
+// π(m) = lcm(π(p^k)) over the prime powers of m, where π(p^k) divides p^(k - 1) * π(p)
+// and π(p) divides p - 1 or 2(p + 1) depending on p mod 5. The exact period is what is left
+// after dividing out every prime that keeps F(d) = 0 and F(d + 1) = 1.
+// Factoring is trial division, which is quick up to `MAX_PISANO_MODULUS`.
+pub fn pisano_period(m: u64) -> Result<u128, ZeroModulus> {
+    if m == 0 {
+        return Err(ZeroModulus);
+    }
+
+    Ok(factor(u128::from(m))
+        .into_iter()
+        .map(|(prime, power)| prime_power_period(prime, power))
+        .fold(1, lcm))
+}
+
+fn prime_power_period(prime: u128, power: u32) -> u128 {
+    let modulus = prime.pow(power) as u64;
+
+    let base = match prime {
+        2 => 3,
+        5 => 20,
+        p if p % 10 == 1 || p % 10 == 9 => p - 1,
+        p => 2 * (p + 1),
+    };
+
+    // a multiple of the period, divided down as far as it goes
+    let mut period = prime.pow(power - 1) * base;
+    let mut primes: Vec<u128> = factor(base).into_iter().map(|(p, _)| p).collect();
+    primes.push(prime);
+
+    for p in primes {
+        while period.is_multiple_of(p) && pair_mod(period / p, modulus) == (0, 1 % modulus) {
+            period /= p;
+        }
+    }
+
+    period
+}
+
+// Prime factors with their powers, smallest first. 1 has none.
+fn factor(mut n: u128) -> Vec<(u128, u32)> {
+    let mut factors = Vec::new();
+    let mut divisor = 2;
+
+    while divisor * divisor <= n {
+        let mut power = 0;
+        while n.is_multiple_of(divisor) {
+            n /= divisor;
+            power += 1;
+        }
+        if power > 0 {
+            factors.push((divisor, power));
+        }
+
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+
+    if n > 1 {
+        factors.push((n, 1));
+    }
+
+    factors
+}
+
+fn lcm(a: u128, b: u128) -> u128 {
+    a / gcd(a, b) * b
+}
+
+fn gcd(a: u128, b: u128) -> u128 {
+    match b {
+        0 => a,
+        _ => gcd(b, a % b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{fibonacci, MAX_U128_INDEX};
+
+    // walks the sequence until (0, 1) comes back
+    fn walk_period(m: u64) -> u128 {
+        let (mut a, mut b) = (0, 1 % m);
+        let mut steps = 0;
+
+        loop {
+            (a, b) = (b, (a + b) % m);
+            steps += 1;
+
+            if (a, b) == (0, 1 % m) {
+                return steps;
+            }
+        }
+    }
+
+    #[test]
+    fn matches_exact_values() {
+        for m in [1, 2, 10, 97, 1_000_000_007, u64::MAX] {
+            for n in 0..=MAX_U128_INDEX {
+                let expected = (fibonacci(n).unwrap() % u128::from(m)) as u64;
+                assert_eq!(fibonacci_mod(n, m), Ok(expected), "F({n}) mod {m}");
+            }
+        }
+    }
+
+    #[test]
+    fn huge_n() {
+        // 10^18 mod 60 = 40 and F(40) = 102334155
+        assert_eq!(fibonacci_mod(1_000_000_000_000_000_000, 10), Ok(5));
+        assert_eq!(
+            fibonacci_mod(1_000_000_000_000_000_000, 1_000_000_000),
+            fibonacci_mod(1_000_000_000_000_000_000 % 1_500_000_000, 1_000_000_000)
+        );
+        assert_eq!(
+            fibonacci_mod(1_000_000_000_000_000_000, 1_000_000_007),
+            Ok(209_783_453)
+        );
+        assert_eq!(fibonacci_mod(5, 0), Err(ZeroModulus));
+    }
+
+    #[test]
+    fn known_periods() {
+        // OEIS A001175
+        let table = [
+            1, 3, 8, 6, 20, 24, 16, 12, 24, 60, 10, 24, 28, 48, 40, 24, 36, 24, 18, 60, 16, 30, 48,
+            24, 100, 84, 72, 48, 14, 120,
+        ];
+
+        for (index, expected) in table.iter().enumerate() {
+            let m = index as u64 + 1;
+            assert_eq!(pisano_period(m), Ok(*expected), "π({m})");
+        }
+
+        assert_eq!(pisano_period(100), Ok(300));
+        assert_eq!(pisano_period(1000), Ok(1500));
+        assert_eq!(pisano_period(1_000_000_000), Ok(1_500_000_000));
+        assert_eq!(pisano_period(1_000_000_007), Ok(2_000_000_016));
+        assert_eq!(pisano_period(0), Err(ZeroModulus));
+    }
+
+    #[test]
+    fn periods_match_walking() {
+        for m in 1..=500 {
+            assert_eq!(pisano_period(m), Ok(walk_period(m)), "π({m})");
+        }
+        // a prime with p mod 5 = 1 and one with 2(p + 1)
+        assert_eq!(pisano_period(1_000_003), Ok(walk_period(1_000_003)));
+        assert_eq!(pisano_period(1_000_033), Ok(walk_period(1_000_033)));
+    }
+}
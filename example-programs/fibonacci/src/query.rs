@@ -0,0 +1,299 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+// More terms than anybody reads on one line. A recurrence that repeats or stays put never
+// runs out of terms that fit, so without a limit a big range or `--list` would print for hours.
+pub const MAX_LIST_TERMS: u64 = 10_000;
+
+// One line typed into the prompt: numbers, ranges or both, separated by commas or spaces.
+// "10", "10..20" (20 left out, like Rust ranges), "10..=20" and "1, 5, 10..=12" all work.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Query {
+    // single numbers are ranges of one
+    items: Vec<RangeInclusive<u64>>,
+}
+
+impl Query {
+    pub fn parse(input: &str) -> Result<Query, QueryError> {
+        let mut items = Vec::new();
+        // char position where the current comma separated part starts
+        let mut start = 0;
+
+        for part in input.split(',') {
+            let mut empty = true;
+
+            for (offset, token) in words(part) {
+                items.push(item(token, start + offset)?);
+                empty = false;
+            }
+
+            // "1,,2" or "1, 2," lost a number, a line with nothing at all is simply no query
+            if empty && input.contains(',') {
+                return Err(QueryError {
+                    kind: QueryErrorKind::Missing,
+                    // the comma that ends the empty part, or the end of the line
+                    position: start + part.chars().count(),
+                    len: 1,
+                });
+            }
+
+            start += part.chars().count() + 1;
+        }
+
+        Ok(Query { items })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    // Every n in the order they were written, ranges counted out lazily.
+    pub fn numbers(&self) -> impl Iterator<Item = u64> + '_ {
+        self.items.iter().flat_map(|range| range.clone())
+    }
+
+    // Exactly one n. Answers to more than one need to say which n they are for.
+    pub fn is_single(&self) -> bool {
+        matches!(self.items.as_slice(), [range] if range.start() == range.end())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QueryErrorKind {
+    // nothing between two commas, or after the last one
+    Missing,
+    InvalidNumber(String),
+    // e.g. "20..10" or "5..5", which have no numbers in them
+    EmptyRange {
+        start: u64,
+        end: u64,
+        inclusive: bool,
+    },
+    // more than `MAX_LIST_TERMS` numbers, e.g. "0..=18446744073709551615"
+    LongRange {
+        start: u64,
+        end: u64,
+        inclusive: bool,
+    },
+}
+
+// A bad token and where it is, counted in chars from 0, so the prompt can point at it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    pub kind: QueryErrorKind,
+    pub position: usize,
+    pub len: usize,
+}
+
+impl QueryError {
+    // `input` with `^` under the bad token on the line below, for the prompt to echo.
+    pub fn pointer(&self, input: &str) -> String {
+        format!(
+            "{input}\n{}{}",
+            " ".repeat(self.position),
+            "^".repeat(self.len.max(1))
+        )
+    }
+}
+
+impl fmt::Display for QueryError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let column = self.position + 1;
+
+        match &self.kind {
+            QueryErrorKind::Missing => write!(f, "expected a number at column {column}"),
+            QueryErrorKind::InvalidNumber(token) => write!(
+                f,
+                "`{token}` at column {column} is not a whole number from 0 to {}",
+                u64::MAX
+            ),
+            QueryErrorKind::EmptyRange {
+                start,
+                end,
+                inclusive,
+            } => {
+                let dots = if *inclusive { "..=" } else { ".." };
+                write!(
+                    f,
+                    "`{start}{dots}{end}` at column {column} has no numbers in it, ranges count up"
+                )
+            }
+            QueryErrorKind::LongRange {
+                start,
+                end,
+                inclusive,
+            } => {
+                let dots = if *inclusive { "..=" } else { ".." };
+                write!(
+                    f,
+                    "`{start}{dots}{end}` at column {column} has more than {MAX_LIST_TERMS} numbers in it"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryError {}
+
+// Whitespace separated words with the char position they start at.
+fn words(text: &str) -> Vec<(usize, &str)> {
+    let mut words = Vec::new();
+    let mut start = None;
+
+    for (position, (index, c)) in text.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((position, index)),
+            (true, Some((first, from))) => {
+                words.push((first, &text[from..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((first, from)) = start {
+        words.push((first, &text[from..]));
+    }
+
+    words
+}
+
+fn item(token: &str, position: usize) -> Result<RangeInclusive<u64>, QueryError> {
+    let (from, to, inclusive) = match token.split_once("..") {
+        None => {
+            let n = number(token, position)?;
+            return Ok(n..=n);
+        }
+        Some((from, to)) => match to.strip_prefix('=') {
+            Some(to) => (from, to, true),
+            None => (from, to, false),
+        },
+    };
+
+    let start = number(from, position)?;
+    // the end starts after the dots
+    let end_position = position + from.chars().count() + if inclusive { 3 } else { 2 };
+    let end = number(to, end_position)?;
+
+    let range = match inclusive {
+        true => start..=end,
+        // `start..0` is empty anyway
+        false => start..=end.saturating_sub(1),
+    };
+
+    if range.is_empty() || (!inclusive && end == 0) {
+        return Err(QueryError {
+            kind: QueryErrorKind::EmptyRange {
+                start,
+                end,
+                inclusive,
+            },
+            position,
+            len: token.chars().count(),
+        });
+    }
+
+    if range.end() - range.start() >= MAX_LIST_TERMS {
+        return Err(QueryError {
+            kind: QueryErrorKind::LongRange {
+                start,
+                end,
+                inclusive,
+            },
+            position,
+            len: token.chars().count(),
+        });
+    }
+
+    Ok(range)
+}
+
+fn number(token: &str, position: usize) -> Result<u64, QueryError> {
+    token.parse().map_err(|_| QueryError {
+        kind: QueryErrorKind::InvalidNumber(token.to_string()),
+        position,
+        len: token.chars().count(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(input: &str) -> Vec<u64> {
+        Query::parse(input).unwrap().numbers().collect()
+    }
+
+    #[test]
+    fn numbers_ranges_and_lists() {
+        assert_eq!(numbers("10"), [10]);
+        assert_eq!(numbers(" 10 "), [10]);
+        assert_eq!(numbers("3..6"), [3, 4, 5]);
+        assert_eq!(numbers("3..=6"), [3, 4, 5, 6]);
+        assert_eq!(numbers("1, 5,7 9..=10"), [1, 5, 7, 9, 10]);
+        assert!(Query::parse("").unwrap().is_empty());
+        assert!(Query::parse("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn single_or_many() {
+        assert!(Query::parse("10").unwrap().is_single());
+        assert!(Query::parse("10..=10").unwrap().is_single());
+        assert!(!Query::parse("10..12").unwrap().is_single());
+        assert!(!Query::parse("1,2").unwrap().is_single());
+    }
+
+    #[test]
+    fn ranges_have_a_limit() {
+        assert_eq!(numbers("0..10000").len(), 10_000);
+        assert_eq!(numbers("1..=10000").len(), 10_000);
+
+        let input = "1, 0..=18446744073709551615";
+        let err = Query::parse(input).unwrap_err();
+        assert!(matches!(err.kind, QueryErrorKind::LongRange { .. }));
+        assert_eq!((err.position, err.len), (3, 24));
+        assert_eq!(
+            err.to_string(),
+            "`0..=18446744073709551615` at column 4 has more than 10000 numbers in it"
+        );
+        assert!(Query::parse("0..=10000").is_err());
+    }
+
+    #[test]
+    fn errors_point_at_the_token() {
+        let input = "1, 2, x3";
+        let err = Query::parse(input).unwrap_err();
+        assert_eq!(err.kind, QueryErrorKind::InvalidNumber(String::from("x3")));
+        assert_eq!(err.position, 6);
+        assert_eq!(err.pointer(input), "1, 2, x3\n      ^^");
+        assert_eq!(
+            err.to_string(),
+            "`x3` at column 7 is not a whole number from 0 to 18446744073709551615"
+        );
+
+        // the bad end of a range, not the whole range
+        let err = Query::parse("5..=1O").unwrap_err();
+        assert_eq!((err.position, err.len), (4, 2));
+
+        let err = Query::parse("é 2").unwrap_err();
+        assert_eq!((err.position, err.len), (0, 1));
+
+        let err = Query::parse("1 20..10").unwrap_err();
+        assert_eq!(err.position, 2);
+        assert!(matches!(err.kind, QueryErrorKind::EmptyRange { .. }));
+        assert!(Query::parse("5..5").is_err());
+        assert!(Query::parse("3..0").is_err());
+        assert!(Query::parse("-1").is_err());
+        assert!(Query::parse("1..").is_err());
+    }
+
+    #[test]
+    fn missing_numbers() {
+        let err = Query::parse("1,,2").unwrap_err();
+        assert_eq!(err.kind, QueryErrorKind::Missing);
+        assert_eq!(err.position, 2);
+
+        assert_eq!(Query::parse("1, 2,").unwrap_err().position, 5);
+        assert_eq!(Query::parse(",1").unwrap_err().position, 0);
+    }
+}
@@ -0,0 +1,399 @@
+use std::collections::VecDeque;
+use std::fmt;
+use std::str::FromStr;
+
+// The whole Fibonacci sequence, one number at a time: 0, 1, 1, 2, 3, 5, ...
+// Nothing is computed before it is asked for, so `Fibonacci::new().skip_while(|f| *f < 1000)` is fine.
+// Stops after F(186), the last one that fits in a u128, instead of returning wrong numbers.
+#[derive(Debug, Clone)]
+pub struct Fibonacci {
+    current: Option<u128>,
+    next: Option<u128>,
+}
+
+impl Default for Fibonacci {
+    fn default() -> Fibonacci {
+        Fibonacci {
+            current: Some(0),
+            next: Some(1),
+        }
+    }
+}
+
+impl Fibonacci {
+    pub fn new() -> Fibonacci {
+        Fibonacci::default()
+    }
+}
+
+impl Iterator for Fibonacci {
+    type Item = u128;
+
+    fn next(&mut self) -> Option<u128> {
+        let current = self.current?;
+
+        self.current = self.next;
+        self.next = self.next.and_then(|next| next.checked_add(current));
+
+        Some(current)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecurrenceError {
+    Empty,
+    // every coefficient needs a seed to start from
+    Mismatch { coefficients: usize, seeds: usize },
+}
+
+impl fmt::Display for RecurrenceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RecurrenceError::Empty => write!(f, "a recurrence needs at least one coefficient"),
+            RecurrenceError::Mismatch {
+                coefficients,
+                seeds,
+            } => write!(
+                f,
+                "{coefficients} coefficients need {coefficients} seeds, got {seeds}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RecurrenceError {}
+
+// a(n) = c1 * a(n - 1) + c2 * a(n - 2) + ... + ck * a(n - k), starting from k seeds a(0) to a(k - 1).
+// Fibonacci is coefficients [1, 1] with seeds [0, 1].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinearRecurrence {
+    coefficients: Vec<i128>,
+    seeds: Vec<i128>,
+}
+
+impl LinearRecurrence {
+    pub fn new(
+        coefficients: Vec<i128>,
+        seeds: Vec<i128>,
+    ) -> Result<LinearRecurrence, RecurrenceError> {
+        if coefficients.is_empty() {
+            return Err(RecurrenceError::Empty);
+        }
+
+        if coefficients.len() != seeds.len() {
+            return Err(RecurrenceError::Mismatch {
+                coefficients: coefficients.len(),
+                seeds: seeds.len(),
+            });
+        }
+
+        Ok(LinearRecurrence {
+            coefficients,
+            seeds,
+        })
+    }
+
+    pub fn coefficients(&self) -> &[i128] {
+        &self.coefficients
+    }
+
+    pub fn seeds(&self) -> &[i128] {
+        &self.seeds
+    }
+
+    // Lazy like `Fibonacci`, and it ends the same way once a term no longer fits in an i128.
+    pub fn terms(&self) -> Terms<'_> {
+        Terms {
+            coefficients: &self.coefficients,
+            window: self.seeds.iter().copied().collect(),
+            overflowed: false,
+        }
+    }
+
+    // a(n), or `None` when it, or a number needed to get to it, is too big for an i128.
+    // The state (a(m), ..., a(m + k - 1)) moves one step by multiplying it with the k x k companion
+    // matrix, so a(n) takes the matrix to the power n - k + 1, O(log n) matrix products whether the
+    // sequence grows, repeats or stays put. The powers can outgrow the terms when negative coefficients
+    // cancel out, close to the start walking the terms still finds those.
+    pub fn term(&self, n: u64) -> Option<i128> {
+        self.term_by_matrix(n).or_else(|| match n < WALK_LIMIT {
+            true => self.terms().nth(n as usize),
+            false => None,
+        })
+    }
+
+    fn term_by_matrix(&self, n: u64) -> Option<i128> {
+        let k = self.coefficients.len();
+
+        if let Some(seed) = usize::try_from(n).ok().and_then(|n| self.seeds.get(n)) {
+            return Some(*seed);
+        }
+
+        // ones above the diagonal shift the state, the last row is the rule itself
+        let mut companion = vec![vec![0i128; k]; k];
+        for (row, cells) in companion.iter_mut().enumerate().take(k - 1) {
+            cells[row + 1] = 1;
+        }
+        for (column, coefficient) in self.coefficients.iter().rev().enumerate() {
+            companion[k - 1][column] = *coefficient;
+        }
+
+        let mut power = identity(k);
+        let mut base = companion;
+        let mut exponent = n - k as u64 + 1;
+
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                power = multiply(&power, &base)?;
+            }
+
+            exponent >>= 1;
+            // squaring once more than needed could overflow for nothing
+            if exponent > 0 {
+                base = multiply(&base, &base)?;
+            }
+        }
+
+        // the last entry of the moved state is a(n)
+        power[k - 1]
+            .iter()
+            .zip(&self.seeds)
+            .try_fold(0i128, |sum, (cell, seed)| {
+                sum.checked_add(cell.checked_mul(*seed)?)
+            })
+    }
+}
+
+// How far `term` walks when the matrix does not fit, a few milliseconds of additions.
+const WALK_LIMIT: u64 = 100_000;
+
+type Matrix = Vec<Vec<i128>>;
+
+fn identity(size: usize) -> Matrix {
+    (0..size)
+        .map(|row| (0..size).map(|column| i128::from(row == column)).collect())
+        .collect()
+}
+
+// `None` as soon as one product or sum does not fit in an i128.
+fn multiply(a: &Matrix, b: &Matrix) -> Option<Matrix> {
+    a.iter()
+        .map(|row| {
+            (0..b.len())
+                .map(|column| {
+                    row.iter().zip(b).try_fold(0i128, |sum, (cell, b_row)| {
+                        sum.checked_add(cell.checked_mul(b_row[column])?)
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+pub struct Terms<'a> {
+    coefficients: &'a [i128],
+    // the next k terms, the front one is returned next
+    window: VecDeque<i128>,
+    overflowed: bool,
+}
+
+impl Iterator for Terms<'_> {
+    type Item = i128;
+
+    fn next(&mut self) -> Option<i128> {
+        // the term after the window needs the whole window, so it is computed before the front leaves
+        if !self.overflowed {
+            let next = self
+                .coefficients
+                .iter()
+                .zip(self.window.iter().rev())
+                .try_fold(0i128, |sum, (coefficient, term)| {
+                    sum.checked_add(coefficient.checked_mul(*term)?)
+                });
+
+            match next {
+                Some(next) => self.window.push_back(next),
+                None => self.overflowed = true,
+            }
+        }
+
+        self.window.pop_front()
+    }
+}
+
+// The well known recurrences, so nobody has to remember their coefficients and seeds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sequence {
+    // 0, 1, 1, 2, 3, 5, 8, ...
+    Fibonacci,
+    // 2, 1, 3, 4, 7, 11, 18, ... same rule as Fibonacci, different start
+    Lucas,
+    // 0, 1, 2, 5, 12, 29, 70, ... a(n) = 2 * a(n - 1) + a(n - 2)
+    Pell,
+    // 0, 0, 1, 1, 2, 4, 7, 13, ... the sum of the last three
+    Tribonacci,
+}
+
+impl Sequence {
+    pub const ALL: [Sequence; 4] = [
+        Sequence::Fibonacci,
+        Sequence::Lucas,
+        Sequence::Pell,
+        Sequence::Tribonacci,
+    ];
+
+    pub fn recurrence(&self) -> LinearRecurrence {
+        let (coefficients, seeds) = match self {
+            Sequence::Fibonacci => (vec![1, 1], vec![0, 1]),
+            Sequence::Lucas => (vec![1, 1], vec![2, 1]),
+            Sequence::Pell => (vec![2, 1], vec![0, 1]),
+            Sequence::Tribonacci => (vec![1, 1, 1], vec![0, 0, 1]),
+        };
+
+        LinearRecurrence {
+            coefficients,
+            seeds,
+        }
+    }
+}
+
+impl fmt::Display for Sequence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Sequence::Fibonacci => "fibonacci",
+            Sequence::Lucas => "lucas",
+            Sequence::Pell => "pell",
+            Sequence::Tribonacci => "tribonacci",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Sequence {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Sequence, String> {
+        match name.trim().to_lowercase().as_str() {
+            "fibonacci" | "fib" => Ok(Sequence::Fibonacci),
+            "lucas" => Ok(Sequence::Lucas),
+            "pell" => Ok(Sequence::Pell),
+            "tribonacci" | "trib" => Ok(Sequence::Tribonacci),
+            _ => Err(format!(
+                "unknown sequence `{name}`, expected fibonacci, lucas, pell or tribonacci"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algorithms::{fibonacci, MAX_U128_INDEX};
+
+    fn first(sequence: Sequence, count: usize) -> Vec<i128> {
+        sequence.recurrence().terms().take(count).collect()
+    }
+
+    #[test]
+    fn iterator_is_lazy_and_works_with_adapters() {
+        let first: Vec<u128> = Fibonacci::new().take(10).collect();
+        assert_eq!(first, [0, 1, 1, 2, 3, 5, 8, 13, 21, 34]);
+
+        let over_1000: Vec<u128> = Fibonacci::new()
+            .skip_while(|f| *f <= 1000)
+            .take(3)
+            .collect();
+        assert_eq!(over_1000, [1597, 2584, 4181]);
+
+        let even: Vec<u128> = Fibonacci::new().filter(|f| f % 2 == 0).take(4).collect();
+        assert_eq!(even, [0, 2, 8, 34]);
+    }
+
+    #[test]
+    fn iterator_ends_before_overflow() {
+        assert_eq!(Fibonacci::new().count() as u64, MAX_U128_INDEX + 1);
+        assert_eq!(Fibonacci::new().last(), fibonacci(MAX_U128_INDEX).ok());
+    }
+
+    #[test]
+    fn named_sequences() {
+        assert_eq!(first(Sequence::Fibonacci, 8), [0, 1, 1, 2, 3, 5, 8, 13]);
+        assert_eq!(first(Sequence::Lucas, 8), [2, 1, 3, 4, 7, 11, 18, 29]);
+        assert_eq!(first(Sequence::Pell, 8), [0, 1, 2, 5, 12, 29, 70, 169]);
+        assert_eq!(
+            first(Sequence::Tribonacci, 10),
+            [0, 0, 1, 1, 2, 4, 7, 13, 24, 44]
+        );
+
+        for sequence in Sequence::ALL {
+            assert_eq!(sequence.to_string().parse(), Ok(sequence));
+        }
+    }
+
+    #[test]
+    fn recurrence_matches_fibonacci() {
+        let recurrence = Sequence::Fibonacci.recurrence();
+
+        assert_eq!(recurrence.term(100), Some(fibonacci(100).unwrap() as i128));
+        // F(185) is the last that fits in an i128
+        assert_eq!(recurrence.terms().count(), 185);
+    }
+
+    #[test]
+    fn custom_recurrence() {
+        // a(n) = a(n - 1) - a(n - 2) repeats every six terms
+        let recurrence = LinearRecurrence::new(vec![1, -1], vec![1, 2]).unwrap();
+        let terms: Vec<i128> = recurrence.terms().take(8).collect();
+        assert_eq!(terms, [1, 2, 1, -1, -2, -1, 1, 2]);
+
+        // powers of three
+        let recurrence = LinearRecurrence::new(vec![3], vec![1]).unwrap();
+        assert_eq!(recurrence.term(4), Some(81));
+        assert_eq!(recurrence.term(80), Some(3i128.pow(80)));
+        assert_eq!(recurrence.term(81), None);
+    }
+
+    #[test]
+    fn term_agrees_with_terms() {
+        let recurrences = Sequence::ALL
+            .iter()
+            .map(|sequence| sequence.recurrence())
+            .chain([LinearRecurrence::new(vec![-2, 0, 3], vec![1, -1, 2]).unwrap()]);
+
+        for recurrence in recurrences {
+            let terms: Vec<i128> = recurrence.terms().collect();
+
+            for (n, term) in terms.iter().enumerate() {
+                assert_eq!(recurrence.term(n as u64), Some(*term), "{recurrence:?} {n}");
+            }
+            assert_eq!(recurrence.term(terms.len() as u64 + 1), None);
+        }
+    }
+
+    #[test]
+    fn term_far_out_when_nothing_grows() {
+        // 1, 2, 1, -1, -2, -1 over and over, 10^12 is 4 more than a multiple of 6
+        let recurrence = LinearRecurrence::new(vec![1, -1], vec![1, 2]).unwrap();
+        assert_eq!(recurrence.term(1_000_000_000_000), Some(-2));
+
+        let constant = LinearRecurrence::new(vec![1], vec![7]).unwrap();
+        assert_eq!(constant.term(u64::MAX), Some(7));
+    }
+
+    #[test]
+    fn bad_recurrences() {
+        assert_eq!(
+            LinearRecurrence::new(vec![], vec![]),
+            Err(RecurrenceError::Empty)
+        );
+        assert_eq!(
+            LinearRecurrence::new(vec![1, 1], vec![1]),
+            Err(RecurrenceError::Mismatch {
+                coefficients: 2,
+                seeds: 1
+            })
+        );
+    }
+}
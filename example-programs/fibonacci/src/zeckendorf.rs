@@ -0,0 +1,231 @@
+use std::fmt;
+
+use crate::sequence::Fibonacci;
+
+// Every positive integer is a sum of Fibonacci numbers where no two are next to each other
+// in the sequence, in exactly one way: 100 = 89 + 8 + 3. Largest first, empty for 0.
+pub fn zeckendorf(mut n: u128) -> Vec<u128> {
+    // F(2) onwards, F(0) = 0 and F(1) = F(2) = 1 would make the sum ambiguous
+    let fibonacci: Vec<u128> = Fibonacci::new().skip(2).take_while(|f| *f <= n).collect();
+    let mut parts = Vec::new();
+
+    // taking the largest that fits every time never takes two neighbours
+    for f in fibonacci.iter().rev() {
+        if *f <= n {
+            parts.push(*f);
+            n -= f;
+        }
+    }
+
+    parts
+}
+
+// Fibonacci coding writes the Zeckendorf sum as bits, F(2) first, and closes it with an extra 1.
+// No sum has two neighbours, so "11" only ever shows up at the end of a number:
+// 1 -> 11, 2 -> 011, 3 -> 0011, 4 -> 1011, 100 -> 00101000011.
+// Small numbers get short codes and no lengths are stored, which is what makes it a universal code.
+//
+// The code has no word for 0, so every value is written as value + 1.
+pub fn encode(values: &[u64]) -> Vec<u8> {
+    let fibonacci: Vec<u128> = Fibonacci::new().skip(2).collect();
+    let mut bits = BitWriter::default();
+
+    for value in values {
+        let mut rest = u128::from(*value) + 1;
+        let mut code = Vec::new();
+        // never `None`, rest is at least 1 = F(2)
+        let top = fibonacci.iter().rposition(|f| *f <= rest).unwrap_or(0);
+
+        // the same greedy walk as `zeckendorf`, remembering positions instead of values
+        for f in fibonacci[..=top].iter().rev() {
+            code.push(*f <= rest);
+            if *f <= rest {
+                rest -= f;
+            }
+        }
+
+        code.reverse();
+        code.push(true);
+        code.into_iter().for_each(|bit| bits.push(bit));
+    }
+
+    bits.finish()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    // the data stops in the middle of a number, `bit` is where that number starts
+    Truncated { bit: usize },
+    // a number that does not fit in a u64
+    TooLarge { bit: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::Truncated { bit } => write!(
+                f,
+                "the number starting at bit {bit} never ends, the data is cut off"
+            ),
+            DecodeError::TooLarge { bit } => {
+                write!(f, "the number starting at bit {bit} is too big for 64 bits")
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+// Reads back what `encode` wrote. The zero bits filling up the last byte are not a number.
+pub fn decode(bytes: &[u8]) -> Result<Vec<u64>, DecodeError> {
+    let fibonacci: Vec<u128> = Fibonacci::new().skip(2).collect();
+    let mut values = Vec::new();
+
+    // the number being read: where it started, its sum so far and its next Fibonacci position
+    let mut start = 0;
+    let mut sum: u128 = 0;
+    let mut position = 0;
+    let mut previous = false;
+
+    let bits = bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |shift| byte >> shift & 1 == 1));
+
+    for (index, bit) in bits.enumerate() {
+        if bit && previous {
+            let value = u64::try_from(sum - 1).map_err(|_| DecodeError::TooLarge { bit: start })?;
+            values.push(value);
+
+            start = index + 1;
+            sum = 0;
+            position = 0;
+            previous = false;
+            continue;
+        }
+
+        if bit {
+            let f = fibonacci
+                .get(position)
+                .ok_or(DecodeError::TooLarge { bit: start })?;
+            sum = sum
+                .checked_add(*f)
+                .ok_or(DecodeError::TooLarge { bit: start })?;
+        }
+
+        position += 1;
+        previous = bit;
+    }
+
+    // anything but a few zeros of padding means the last number was cut off
+    if sum != 0 || bytes.len() * 8 - start >= 8 {
+        return Err(DecodeError::Truncated { bit: start });
+    }
+
+    Ok(values)
+}
+
+// Packs bits into bytes, highest bit first.
+#[derive(Debug, Default)]
+struct BitWriter {
+    bytes: Vec<u8>,
+    used: u32,
+}
+
+impl BitWriter {
+    fn push(&mut self, bit: bool) {
+        if self.used.is_multiple_of(8) {
+            self.bytes.push(0);
+        }
+
+        if bit {
+            let last = self.bytes.len() - 1;
+            self.bytes[last] |= 1 << (7 - self.used % 8);
+        }
+
+        self.used += 1;
+    }
+
+    fn finish(self) -> Vec<u8> {
+        self.bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bits(bytes: &[u8], count: usize) -> String {
+        bytes
+            .iter()
+            .map(|byte| format!("{byte:08b}"))
+            .collect::<String>()[..count]
+            .to_string()
+    }
+
+    #[test]
+    fn decomposition() {
+        assert_eq!(zeckendorf(0), Vec::<u128>::new());
+        assert_eq!(zeckendorf(1), [1]);
+        assert_eq!(zeckendorf(4), [3, 1]);
+        assert_eq!(zeckendorf(100), [89, 8, 3]);
+        assert_eq!(zeckendorf(u128::MAX).iter().sum::<u128>(), u128::MAX);
+    }
+
+    #[test]
+    fn no_two_neighbours() {
+        let fibonacci: Vec<u128> = Fibonacci::new().skip(2).take(40).collect();
+
+        for n in 1..2000 {
+            let parts = zeckendorf(n);
+            assert_eq!(parts.iter().sum::<u128>(), n);
+
+            let positions: Vec<usize> = parts
+                .iter()
+                .map(|part| fibonacci.iter().position(|f| f == part).unwrap())
+                .collect();
+            assert!(positions.windows(2).all(|pair| pair[0] >= pair[1] + 2));
+        }
+    }
+
+    #[test]
+    fn known_codes() {
+        // values are shifted by one, 0 is written as the code of 1
+        assert_eq!(bits(&encode(&[0]), 2), "11");
+        assert_eq!(bits(&encode(&[1]), 3), "011");
+        assert_eq!(bits(&encode(&[3]), 4), "1011");
+        assert_eq!(bits(&encode(&[99]), 11), "00101000011");
+        assert_eq!(encode(&[0, 0, 0, 0]), [0b1111_1111]);
+        assert_eq!(encode(&[]), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn round_trip() {
+        let values: Vec<u64> = (0..1000)
+            .chain([u32::MAX as u64, u64::MAX - 1, u64::MAX])
+            .collect();
+
+        assert_eq!(decode(&encode(&values)), Ok(values));
+
+        for value in [0, 7, 100, u64::MAX] {
+            assert_eq!(decode(&encode(&[value])), Ok(vec![value]));
+        }
+    }
+
+    #[test]
+    fn broken_data() {
+        // "011" for 1 and then "01" that never ends
+        assert_eq!(
+            decode(&[0b0110_1000]),
+            Err(DecodeError::Truncated { bit: 3 })
+        );
+        // a whole byte of zeros is not padding
+        let mut bytes = encode(&[5]);
+        bytes.push(0);
+        assert!(matches!(decode(&bytes), Err(DecodeError::Truncated { .. })));
+        // "10" over and over never ends a number and soon adds up past 64 bits
+        assert_eq!(
+            decode(&[0b1010_1010; 30]),
+            Err(DecodeError::TooLarge { bit: 0 })
+        );
+    }
+}
@@ -0,0 +1,256 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+use crate::player::Guesser;
+
+// Bots only learn about the secret through the same "Too small"/"Too big" feedback a person gets.
+// All of them keep the smallest window (`low..=high`) that can still contain the secret.
+#[derive(Debug, Clone, Copy)]
+struct Window {
+    low: u32,
+    high: u32,
+}
+
+impl Window {
+    fn new(range: &RangeInclusive<u32>) -> Window {
+        Window {
+            low: *range.start(),
+            high: *range.end(),
+        }
+    }
+
+    fn narrow(&mut self, guess: u32, ordering: Ordering) {
+        match ordering {
+            Ordering::Less => self.low = self.low.max(guess.saturating_add(1)),
+            Ordering::Greater => self.high = self.high.min(guess.saturating_sub(1)),
+            Ordering::Equal => {
+                self.low = guess;
+                self.high = guess;
+            }
+        }
+    }
+
+    fn middle(&self) -> u32 {
+        self.low + (self.high.saturating_sub(self.low)) / 2
+    }
+
+    // When feedback contradicts itself the window can end up empty, guessing stops then.
+    fn is_empty(&self) -> bool {
+        self.low > self.high
+    }
+}
+
+// Always guesses the middle of what is left, needs at most log2(range) + 1 attempts.
+pub struct BinaryBot {
+    window: Window,
+}
+
+impl BinaryBot {
+    pub fn new(range: RangeInclusive<u32>) -> BinaryBot {
+        BinaryBot {
+            window: Window::new(&range),
+        }
+    }
+}
+
+impl Guesser for BinaryBot {
+    fn next_guess(&mut self) -> Option<u32> {
+        (!self.window.is_empty()).then(|| self.window.middle())
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering) {
+        self.window.narrow(guess, ordering);
+    }
+}
+
+// Picks any number that is still possible, so it never wastes a guess but does not split evenly either.
+pub struct RandomBot {
+    window: Window,
+    rng: StdRng,
+}
+
+impl RandomBot {
+    pub fn new(range: RangeInclusive<u32>, seed: u64) -> RandomBot {
+        RandomBot {
+            window: Window::new(&range),
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl Guesser for RandomBot {
+    fn next_guess(&mut self) -> Option<u32> {
+        (!self.window.is_empty()).then(|| self.rng.gen_range(self.window.low..=self.window.high))
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering) {
+        self.window.narrow(guess, ordering);
+    }
+}
+
+// Plays like a person would: aims roughly at the middle but not exactly,
+// and once in a while just nudges the last guess by one instead of thinking.
+pub struct NoisyBot {
+    window: Window,
+    rng: StdRng,
+    last: Option<(u32, Ordering)>,
+}
+
+impl NoisyBot {
+    // how often the bot nudges its last guess instead of aiming
+    const NUDGE_CHANCE: f64 = 0.15;
+
+    pub fn new(range: RangeInclusive<u32>, seed: u64) -> NoisyBot {
+        NoisyBot {
+            window: Window::new(&range),
+            rng: StdRng::seed_from_u64(seed),
+            last: None,
+        }
+    }
+}
+
+impl Guesser for NoisyBot {
+    fn next_guess(&mut self) -> Option<u32> {
+        if self.window.is_empty() {
+            return None;
+        }
+
+        let Window { low, high } = self.window;
+
+        if let Some((last, ordering)) = self.last {
+            if self.rng.gen_bool(NoisyBot::NUDGE_CHANCE) {
+                let nudged = match ordering {
+                    Ordering::Less => last.saturating_add(1),
+                    _ => last.saturating_sub(1),
+                };
+
+                return Some(nudged.clamp(low, high));
+            }
+        }
+
+        // up to a quarter of the window away from the middle, in either direction
+        let spread = (high - low) / 4;
+        let aim = self.window.middle();
+        let guess = self
+            .rng
+            .gen_range(aim.saturating_sub(spread)..=aim.saturating_add(spread));
+
+        Some(guess.clamp(low, high))
+    }
+
+    fn feedback(&mut self, guess: u32, ordering: Ordering) {
+        self.window.narrow(guess, ordering);
+        self.last = Some((guess, ordering));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BotKind {
+    Binary,
+    Random,
+    Noisy,
+}
+
+impl BotKind {
+    pub fn build(&self, range: RangeInclusive<u32>, seed: u64) -> Box<dyn Guesser> {
+        match self {
+            BotKind::Binary => Box::new(BinaryBot::new(range)),
+            BotKind::Random => Box::new(RandomBot::new(range, seed)),
+            BotKind::Noisy => Box::new(NoisyBot::new(range, seed)),
+        }
+    }
+}
+
+impl fmt::Display for BotKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            BotKind::Binary => "binary",
+            BotKind::Random => "random",
+            BotKind::Noisy => "noisy",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for BotKind {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<BotKind, String> {
+        match name.trim().to_lowercase().as_str() {
+            "binary" => Ok(BotKind::Binary),
+            "random" => Ok(BotKind::Random),
+            "noisy" | "human" => Ok(BotKind::Noisy),
+            _ => Err(format!(
+                "unknown bot `{name}`, expected binary, random or noisy"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::{GameSession, Status};
+
+    fn play(bot: &mut dyn Guesser, secret: u32, range: RangeInclusive<u32>) -> (Status, u32) {
+        let mut session = GameSession::with_secret(secret, range, None);
+
+        while let Some(guess) = bot.next_guess() {
+            let result = session.guess(guess).unwrap();
+            bot.feedback(guess, result.ordering);
+
+            if session.is_over() {
+                break;
+            }
+        }
+
+        (session.status(), session.attempts())
+    }
+
+    #[test]
+    fn binary_bot_needs_at_most_log2_attempts() {
+        for secret in 1..=100 {
+            let (status, attempts) = play(&mut BinaryBot::new(1..=100), secret, 1..=100);
+
+            assert_eq!(status, Status::Won);
+            assert!(attempts <= 7, "secret {secret} took {attempts} attempts");
+        }
+    }
+
+    #[test]
+    fn every_bot_finds_the_secret() {
+        for kind in [BotKind::Binary, BotKind::Random, BotKind::Noisy] {
+            for secret in [1, 2, 500, 999, 1000] {
+                let mut bot = kind.build(1..=1000, secret as u64);
+
+                assert_eq!(
+                    play(bot.as_mut(), secret, 1..=1000).0,
+                    Status::Won,
+                    "{kind} bot"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn contradicting_feedback_stops_the_bot() {
+        let mut bot = BinaryBot::new(1..=10);
+
+        bot.feedback(5, Ordering::Less);
+        bot.feedback(6, Ordering::Greater);
+
+        assert_eq!(bot.next_guess(), None);
+    }
+
+    #[test]
+    fn bot_names() {
+        assert_eq!("Binary".parse(), Ok(BotKind::Binary));
+        assert_eq!("human".parse(), Ok(BotKind::Noisy));
+        assert!("smart".parse::<BotKind>().is_err());
+    }
+}
@@ -0,0 +1,366 @@
+use std::path::PathBuf;
+
+use crate::bot::BotKind;
+use crate::difficulty::{Difficulty, Settings};
+use crate::hints::HintMode;
+use crate::scoreboard::DEFAULT_SCOREBOARD_FILE;
+
+// How many entries `--leaderboard` prints per group when no number is given.
+pub const DEFAULT_LEADERBOARD_SIZE: usize = 10;
+
+// How many players `--host` waits for when `--players` is not given.
+pub const DEFAULT_PLAYERS: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    Play,
+    Leaderboard {
+        top: usize,
+    },
+    Replay {
+        transcript: PathBuf,
+    },
+    Simulate {
+        games: usize,
+    },
+    Host {
+        address: String,
+        players: usize,
+    },
+    Join {
+        address: String,
+    },
+    // the five letter word variant, `word_list` replaces the built-in list
+    Words {
+        word_list: Option<PathBuf>,
+        daily: bool,
+    },
+    // the player thinks of a number and the computer guesses it
+    Reverse,
+}
+
+// Everything the binary needs to know from the command line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub mode: Mode,
+    pub settings: Settings,
+    pub player: Option<String>,
+    pub scoreboard: PathBuf,
+    // `None` means a random seed is picked when the game starts
+    pub seed: Option<u64>,
+    // where the current game is recorded, nothing is recorded when it is `None`
+    pub transcript: Option<PathBuf>,
+    // a bot plays instead of reading guesses from stdin
+    pub bot: Option<BotKind>,
+    pub hints: HintMode,
+    // full-screen terminal UI instead of one line per guess
+    pub tui: bool,
+}
+
+impl Config {
+    // First item of `args` is the program name, just like `std::env::args()` gives it.
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+
+        let mut config = Config {
+            mode: Mode::Play,
+            settings: Settings::default(),
+            player: None,
+            scoreboard: PathBuf::from(DEFAULT_SCOREBOARD_FILE),
+            seed: None,
+            transcript: None,
+            bot: None,
+            hints: HintMode::Classic,
+            tui: false,
+        };
+
+        // `peekable` lets `--leaderboard` look at the next argument without consuming a flag
+        let mut args = args.peekable();
+
+        // game settings are collected first and validated together once every argument is read
+        let mut difficulty = None;
+        let mut min = None;
+        let mut max = None;
+        let mut attempts = None;
+        // only used together with `--host`
+        let mut players = None;
+        // only used together with `--words`
+        let mut word_list = None;
+        let mut daily = false;
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--leaderboard" => {
+                    let top = match args.peek() {
+                        Some(next) if !next.starts_with("--") => {
+                            let next = args.next().unwrap();
+                            next.parse()
+                                .map_err(|_| format!("`{next}` is not a valid leaderboard size"))?
+                        }
+                        _ => DEFAULT_LEADERBOARD_SIZE,
+                    };
+                    config.mode = Mode::Leaderboard { top };
+                }
+                "--difficulty" => {
+                    difficulty = Some(
+                        value_of(&arg, args.next())?
+                            .parse::<Difficulty>()
+                            .map_err(|err| err.to_string())?,
+                    )
+                }
+                "--min" => min = Some(number_of(&arg, args.next())?),
+                "--max" => max = Some(number_of(&arg, args.next())?),
+                "--attempts" => attempts = Some(number_of(&arg, args.next())?),
+                "--seed" => {
+                    let seed = value_of(&arg, args.next())?;
+                    config.seed = Some(
+                        seed.parse()
+                            .map_err(|_| format!("`{seed}` is not a valid seed"))?,
+                    );
+                }
+                "--transcript" => {
+                    config.transcript = Some(PathBuf::from(value_of(&arg, args.next())?))
+                }
+                "--replay" => {
+                    config.mode = Mode::Replay {
+                        transcript: PathBuf::from(value_of(&arg, args.next())?),
+                    }
+                }
+                "--simulate" => {
+                    let games = value_of(&arg, args.next())?;
+                    config.mode = Mode::Simulate {
+                        games: games
+                            .parse()
+                            .map_err(|_| format!("`{games}` is not a valid number of games"))?,
+                    };
+                }
+                "--bot" => config.bot = Some(value_of(&arg, args.next())?.parse()?),
+                "--hints" => config.hints = value_of(&arg, args.next())?.parse()?,
+                "--host" => {
+                    config.mode = Mode::Host {
+                        address: value_of(&arg, args.next())?,
+                        players: 0,
+                    }
+                }
+                "--players" => players = Some(number_of(&arg, args.next())? as usize),
+                "--join" => {
+                    config.mode = Mode::Join {
+                        address: value_of(&arg, args.next())?,
+                    }
+                }
+                "--words" => {
+                    config.mode = Mode::Words {
+                        word_list: None,
+                        daily: false,
+                    }
+                }
+                "--reverse" => config.mode = Mode::Reverse,
+                "--word-list" => word_list = Some(PathBuf::from(value_of(&arg, args.next())?)),
+                "--daily" => daily = true,
+                "--tui" => config.tui = true,
+                "--name" => config.player = Some(value_of(&arg, args.next())?),
+                "--scoreboard" => config.scoreboard = PathBuf::from(value_of(&arg, args.next())?),
+                other => return Err(format!("unknown argument `{other}`")),
+            }
+        }
+
+        if let Mode::Host {
+            players: ref mut expected,
+            ..
+        } = config.mode
+        {
+            *expected = players.unwrap_or(DEFAULT_PLAYERS);
+
+            if *expected == 0 {
+                return Err(String::from("`--players` must be at least 1"));
+            }
+        }
+
+        if let Mode::Words {
+            word_list: ref mut list,
+            daily: ref mut is_daily,
+        } = config.mode
+        {
+            *list = word_list;
+            *is_daily = daily;
+        }
+
+        config.settings =
+            Settings::build(difficulty, min, max, attempts).map_err(|err| err.to_string())?;
+
+        Ok(config)
+    }
+}
+
+fn value_of(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("`{flag}` needs a value"))
+}
+
+fn number_of(flag: &str, value: Option<String>) -> Result<u32, String> {
+    let value = value_of(flag, value)?;
+
+    value.parse().map_err(|_| {
+        format!("`{value}` is not a valid value for `{flag}`, expected a positive whole number")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Config, String> {
+        let args = std::iter::once("guessing_game")
+            .chain(args.iter().copied())
+            .map(String::from);
+
+        Config::build(args)
+    }
+
+    #[test]
+    fn no_arguments_plays() {
+        let config = build(&[]).unwrap();
+
+        assert_eq!(config.mode, Mode::Play);
+        assert_eq!(config.scoreboard, PathBuf::from(DEFAULT_SCOREBOARD_FILE));
+    }
+
+    #[test]
+    fn leaderboard_with_and_without_size() {
+        assert_eq!(
+            build(&["--leaderboard"]).unwrap().mode,
+            Mode::Leaderboard {
+                top: DEFAULT_LEADERBOARD_SIZE
+            }
+        );
+        assert_eq!(
+            build(&["--leaderboard", "3", "--name", "x"]).unwrap().mode,
+            Mode::Leaderboard { top: 3 }
+        );
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        assert!(build(&["--leaderboard", "many"]).is_err());
+        assert!(build(&["--name"]).is_err());
+        assert!(build(&["--what"]).is_err());
+    }
+
+    #[test]
+    fn difficulty_and_custom_range() {
+        let config = build(&["--difficulty", "easy"]).unwrap();
+        assert_eq!(config.settings.difficulty, Difficulty::Easy);
+
+        let config = build(&["--min", "5", "--max", "50", "--attempts", "3"]).unwrap();
+        assert_eq!(config.settings.difficulty, Difficulty::Custom);
+        assert_eq!(config.settings.range, 5..=50);
+        assert_eq!(config.settings.max_attempts, Some(3));
+    }
+
+    #[test]
+    fn seed_transcript_and_replay() {
+        let config = build(&["--seed", "42", "--transcript", "game.jsonl"]).unwrap();
+        assert_eq!(config.seed, Some(42));
+        assert_eq!(config.transcript, Some(PathBuf::from("game.jsonl")));
+
+        let config = build(&["--replay", "game.jsonl"]).unwrap();
+        assert_eq!(
+            config.mode,
+            Mode::Replay {
+                transcript: PathBuf::from("game.jsonl")
+            }
+        );
+
+        assert!(build(&["--seed", "soon"]).is_err());
+    }
+
+    #[test]
+    fn simulate_with_bot() {
+        let config = build(&["--simulate", "1000", "--bot", "noisy"]).unwrap();
+
+        assert_eq!(config.mode, Mode::Simulate { games: 1000 });
+        assert_eq!(config.bot, Some(BotKind::Noisy));
+        assert!(build(&["--bot", "clever"]).is_err());
+        assert!(build(&["--simulate", "lots"]).is_err());
+    }
+
+    #[test]
+    fn hint_mode() {
+        assert_eq!(build(&[]).unwrap().hints, HintMode::Classic);
+        assert_eq!(
+            build(&["--hints", "warmer-colder"]).unwrap().hints,
+            HintMode::WarmerColder
+        );
+        assert!(build(&["--hints", "psychic"]).is_err());
+    }
+
+    #[test]
+    fn tui_flag() {
+        assert!(!build(&[]).unwrap().tui);
+        assert!(build(&["--tui"]).unwrap().tui);
+    }
+
+    #[test]
+    fn host_and_join() {
+        assert_eq!(
+            build(&["--host", "127.0.0.1:7878"]).unwrap().mode,
+            Mode::Host {
+                address: String::from("127.0.0.1:7878"),
+                players: DEFAULT_PLAYERS
+            }
+        );
+        assert_eq!(
+            build(&["--players", "3", "--host", "127.0.0.1:7878"])
+                .unwrap()
+                .mode,
+            Mode::Host {
+                address: String::from("127.0.0.1:7878"),
+                players: 3
+            }
+        );
+        assert_eq!(
+            build(&["--join", "127.0.0.1:7878"]).unwrap().mode,
+            Mode::Join {
+                address: String::from("127.0.0.1:7878")
+            }
+        );
+        assert!(build(&["--host", "127.0.0.1:7878", "--players", "0"]).is_err());
+    }
+
+    #[test]
+    fn word_game() {
+        assert_eq!(
+            build(&["--words"]).unwrap().mode,
+            Mode::Words {
+                word_list: None,
+                daily: false
+            }
+        );
+        assert_eq!(
+            build(&["--daily", "--words", "--word-list", "mine.txt"])
+                .unwrap()
+                .mode,
+            Mode::Words {
+                word_list: Some(PathBuf::from("mine.txt")),
+                daily: true
+            }
+        );
+        assert!(build(&["--words", "--word-list"]).is_err());
+    }
+
+    #[test]
+    fn reverse_uses_range() {
+        let config = build(&["--reverse", "--difficulty", "hard"]).unwrap();
+
+        assert_eq!(config.mode, Mode::Reverse);
+        assert_eq!(config.settings.range, 1..=10000);
+    }
+
+    #[test]
+    fn bad_range_is_explained() {
+        let err = build(&["--min", "50", "--max", "5"]).unwrap_err();
+        assert!(err.contains("50..=5"), "error was: {err}");
+
+        let err = build(&["--min", "-3"]).unwrap_err();
+        assert!(err.contains("`-3`"), "error was: {err}");
+    }
+}
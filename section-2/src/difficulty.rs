@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// Named presets. `Custom` is used whenever the range or attempt limit was given by hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Custom,
+}
+
+impl Difficulty {
+    pub fn range(&self) -> RangeInclusive<u32> {
+        match self {
+            Difficulty::Easy => 1..=10,
+            Difficulty::Normal | Difficulty::Custom => 1..=100,
+            Difficulty::Hard => 1..=10_000,
+        }
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        match self {
+            Difficulty::Easy => Some(5),
+            Difficulty::Normal => Some(10),
+            Difficulty::Hard => Some(8),
+            Difficulty::Custom => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy => "easy",
+            Difficulty::Normal => "normal",
+            Difficulty::Hard => "hard",
+            Difficulty::Custom => "custom",
+        }
+    }
+}
+
+impl fmt::Display for Difficulty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for Difficulty {
+    type Err = SettingsError;
+
+    fn from_str(name: &str) -> Result<Difficulty, SettingsError> {
+        match name.trim().to_lowercase().as_str() {
+            "easy" => Ok(Difficulty::Easy),
+            "normal" => Ok(Difficulty::Normal),
+            "hard" => Ok(Difficulty::Hard),
+            "custom" => Ok(Difficulty::Custom),
+            _ => Err(SettingsError::UnknownDifficulty(name.to_string())),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettingsError {
+    UnknownDifficulty(String),
+    EmptyRange { min: u32, max: u32 },
+    ZeroAttempts,
+}
+
+impl fmt::Display for SettingsError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SettingsError::UnknownDifficulty(name) => write!(
+                f,
+                "unknown difficulty `{name}`, expected easy, normal or hard"
+            ),
+            SettingsError::EmptyRange { min, max } => write!(
+                f,
+                "range {min}..={max} is invalid, --min must be smaller than --max"
+            ),
+            SettingsError::ZeroAttempts => write!(f, "--attempts must be at least 1"),
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+// The rules one game is played with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Settings {
+    pub difficulty: Difficulty,
+    pub range: RangeInclusive<u32>,
+    pub max_attempts: Option<u32>,
+}
+
+impl Settings {
+    // Starts from the preset (normal when none is given). Anything set by hand turns it into a custom game,
+    // values that were not given are kept from the preset.
+    pub fn build(
+        difficulty: Option<Difficulty>,
+        min: Option<u32>,
+        max: Option<u32>,
+        attempts: Option<u32>,
+    ) -> Result<Settings, SettingsError> {
+        let preset = difficulty.unwrap_or(Difficulty::Normal);
+        let is_custom =
+            preset == Difficulty::Custom || min.is_some() || max.is_some() || attempts.is_some();
+
+        let min = min.unwrap_or(*preset.range().start());
+        let max = max.unwrap_or(*preset.range().end());
+
+        // a range with a single number would not be much of a game
+        if min >= max {
+            return Err(SettingsError::EmptyRange { min, max });
+        }
+
+        if attempts == Some(0) {
+            return Err(SettingsError::ZeroAttempts);
+        }
+
+        Ok(Settings {
+            difficulty: if is_custom {
+                Difficulty::Custom
+            } else {
+                preset
+            },
+            range: min..=max,
+            max_attempts: attempts.or(preset.max_attempts()),
+        })
+    }
+
+    pub fn min(&self) -> u32 {
+        *self.range.start()
+    }
+
+    pub fn max(&self) -> u32 {
+        *self.range.end()
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Settings {
+        Settings::build(None, None, None, None).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets() {
+        let hard = Settings::build(Some(Difficulty::Hard), None, None, None).unwrap();
+
+        assert_eq!(hard.difficulty, Difficulty::Hard);
+        assert_eq!(hard.range, 1..=10_000);
+        assert_eq!(hard.max_attempts, Some(8));
+        assert!(hard.max_attempts < Difficulty::Normal.max_attempts());
+        assert_eq!(Settings::default().difficulty, Difficulty::Normal);
+    }
+
+    #[test]
+    fn custom_values_override_preset() {
+        let settings = Settings::build(Some(Difficulty::Easy), None, Some(50), None).unwrap();
+
+        assert_eq!(settings.difficulty, Difficulty::Custom);
+        assert_eq!(settings.range, 1..=50);
+        assert_eq!(settings.max_attempts, Some(5));
+    }
+
+    #[test]
+    fn invalid_settings() {
+        assert_eq!(
+            Settings::build(None, Some(10), Some(5), None),
+            Err(SettingsError::EmptyRange { min: 10, max: 5 })
+        );
+        assert_eq!(
+            Settings::build(None, Some(5), Some(5), None),
+            Err(SettingsError::EmptyRange { min: 5, max: 5 })
+        );
+        assert_eq!(
+            Settings::build(None, None, None, Some(0)),
+            Err(SettingsError::ZeroAttempts)
+        );
+        assert!("impossible".parse::<Difficulty>().is_err());
+    }
+}
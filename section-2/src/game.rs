@@ -0,0 +1,298 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::hints::Clue;
+
+// State of a round. A round starts as `Playing` and ends either with a correct guess (`Won`)
+// or by running out of attempts (`Lost`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Status {
+    Playing,
+    Won,
+    Lost,
+}
+
+// What the engine answers for a single accepted guess.
+// `ordering` is the comparison of the guess against the secret, so `Ordering::Less` means "Too small".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GuessResult {
+    pub ordering: Ordering,
+    pub status: Status,
+}
+
+// Guesses that the engine refuses. Refused guesses do not count as an attempt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GuessError {
+    OutOfRange { guess: u32, min: u32, max: u32 },
+    GameOver,
+}
+
+impl fmt::Display for GuessError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            GuessError::OutOfRange { guess, min, max } => {
+                write!(f, "{guess} is not between {min} and {max}. Guess again!")
+            }
+            GuessError::GameOver => write!(f, "The game is already over."),
+        }
+    }
+}
+
+impl std::error::Error for GuessError {}
+
+// One round of the guessing game.
+// The secret is drawn once when the session is created and stays the same until the round ends,
+// every accepted guess is counted and compared against `max_attempts` if there is a limit.
+#[derive(Debug)]
+pub struct GameSession {
+    secret: u32,
+    range: RangeInclusive<u32>,
+    max_attempts: Option<u32>,
+    attempts: u32,
+    status: Status,
+}
+
+impl GameSession {
+    // Starts a round with a random secret inside of `range`.
+    pub fn new(range: RangeInclusive<u32>, max_attempts: Option<u32>) -> GameSession {
+        // firstly we are calling `thread_rng` function and it returns a number generater
+        GameSession::from_rng(&mut rand::thread_rng(), range, max_attempts)
+    }
+
+    // Same seed, range and limit always give the same secret, so a reported game can be played again.
+    // Seeds are only reproducible with the same `rand` version since `StdRng` may change between releases.
+    pub fn with_seed(
+        seed: u64,
+        range: RangeInclusive<u32>,
+        max_attempts: Option<u32>,
+    ) -> GameSession {
+        GameSession::from_rng(&mut StdRng::seed_from_u64(seed), range, max_attempts)
+    }
+
+    pub fn from_rng(
+        rng: &mut impl Rng,
+        range: RangeInclusive<u32>,
+        max_attempts: Option<u32>,
+    ) -> GameSession {
+        // we call `gen_range` to generate number, this function takes a range expression
+        // `start..=end` is a range expression and it includes both start and end values
+        let secret = rng.gen_range(range.clone());
+
+        GameSession::with_secret(secret, range, max_attempts)
+    }
+
+    // Starts a round with a known secret, this is what tests use.
+    pub fn with_secret(
+        secret: u32,
+        range: RangeInclusive<u32>,
+        max_attempts: Option<u32>,
+    ) -> GameSession {
+        if !range.contains(&secret) {
+            panic!(
+                "Secret must be between {} and {}, got {}",
+                range.start(),
+                range.end(),
+                secret
+            )
+        }
+
+        GameSession {
+            secret,
+            range,
+            max_attempts,
+            attempts: 0,
+            status: Status::Playing,
+        }
+    }
+
+    pub fn guess(&mut self, guess: u32) -> Result<GuessResult, GuessError> {
+        if self.is_over() {
+            return Err(GuessError::GameOver);
+        }
+
+        if !self.range.contains(&guess) {
+            return Err(GuessError::OutOfRange {
+                guess,
+                min: *self.range.start(),
+                max: *self.range.end(),
+            });
+        }
+
+        self.attempts += 1;
+
+        let ordering = guess.cmp(&self.secret);
+
+        self.status = match ordering {
+            Ordering::Equal => Status::Won,
+            _ if self.remaining_attempts() == Some(0) => Status::Lost,
+            _ => Status::Playing,
+        };
+
+        Ok(GuessResult {
+            ordering,
+            status: self.status,
+        })
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.attempts
+    }
+
+    pub fn max_attempts(&self) -> Option<u32> {
+        self.max_attempts
+    }
+
+    // `None` means there is no attempt limit for this round.
+    pub fn remaining_attempts(&self) -> Option<u32> {
+        self.max_attempts
+            .map(|max| max.saturating_sub(self.attempts))
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn is_over(&self) -> bool {
+        self.status != Status::Playing
+    }
+
+    // What hint strategies get to see about a guess that was just accepted.
+    pub fn clue(&self, guess: u32, ordering: Ordering) -> Clue {
+        Clue {
+            guess,
+            secret: self.secret,
+            ordering,
+            misses: if ordering == Ordering::Equal {
+                self.attempts - 1
+            } else {
+                self.attempts
+            },
+            range: self.range.clone(),
+        }
+    }
+
+    pub fn summary(&self) -> Summary {
+        Summary {
+            secret: self.secret,
+            attempts: self.attempts,
+            max_attempts: self.max_attempts,
+            status: self.status,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub secret: u32,
+    pub attempts: u32,
+    pub max_attempts: Option<u32>,
+    pub status: Status,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let tries = if self.attempts == 1 { "try" } else { "tries" };
+
+        match self.status {
+            Status::Won => write!(f, "You found {} in {} {tries}.", self.secret, self.attempts),
+            Status::Lost => write!(
+                f,
+                "Out of attempts! The number was {} ({} {tries}).",
+                self.secret, self.attempts
+            ),
+            Status::Playing => write!(f, "Still playing, {} {tries} so far.", self.attempts),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secret_does_not_change_between_guesses() {
+        let mut session = GameSession::with_secret(42, 1..=100, None);
+
+        assert_eq!(session.guess(10).unwrap().ordering, Ordering::Less);
+        assert_eq!(session.guess(90).unwrap().ordering, Ordering::Greater);
+        assert_eq!(session.guess(50).unwrap().ordering, Ordering::Greater);
+
+        let result = session.guess(42).unwrap();
+        assert_eq!(result.ordering, Ordering::Equal);
+        assert_eq!(result.status, Status::Won);
+        assert_eq!(session.attempts(), 4);
+    }
+
+    #[test]
+    fn random_secret_is_inside_range() {
+        for _ in 0..100 {
+            let session = GameSession::new(5..=7, None);
+            assert!((5..=7).contains(&session.summary().secret));
+        }
+    }
+
+    #[test]
+    fn same_seed_same_secret() {
+        let first = GameSession::with_seed(1234, 1..=10_000, None);
+        let second = GameSession::with_seed(1234, 1..=10_000, None);
+
+        assert_eq!(first.summary().secret, second.summary().secret);
+    }
+
+    #[test]
+    fn running_out_of_attempts_loses() {
+        let mut session = GameSession::with_secret(7, 1..=10, Some(2));
+
+        assert_eq!(session.guess(1).unwrap().status, Status::Playing);
+        assert_eq!(session.remaining_attempts(), Some(1));
+        assert_eq!(session.guess(2).unwrap().status, Status::Lost);
+        assert_eq!(session.guess(7), Err(GuessError::GameOver));
+    }
+
+    #[test]
+    fn winning_on_last_attempt_is_a_win() {
+        let mut session = GameSession::with_secret(7, 1..=10, Some(2));
+
+        session.guess(1).unwrap();
+        assert_eq!(session.guess(7).unwrap().status, Status::Won);
+    }
+
+    #[test]
+    fn out_of_range_guess_is_not_counted() {
+        let mut session = GameSession::with_secret(7, 1..=10, Some(1));
+
+        assert_eq!(
+            session.guess(11),
+            Err(GuessError::OutOfRange {
+                guess: 11,
+                min: 1,
+                max: 10
+            })
+        );
+        assert_eq!(session.attempts(), 0);
+        assert_eq!(session.status(), Status::Playing);
+    }
+
+    #[test]
+    fn summary_reports_result() {
+        let mut session = GameSession::with_secret(3, 1..=10, None);
+        session.guess(3).unwrap();
+
+        assert_eq!(session.summary().to_string(), "You found 3 in 1 try.");
+    }
+
+    #[test]
+    #[should_panic(expected = "Secret must be between 1 and 10")]
+    fn secret_outside_range_panics() {
+        GameSession::with_secret(11, 1..=10, None);
+    }
+}
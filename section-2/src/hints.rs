@@ -0,0 +1,254 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// Everything a hint strategy may look at after a missed guess.
+// Strategies see the secret, it is up to each one how much of it they give away.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Clue {
+    pub guess: u32,
+    pub secret: u32,
+    pub ordering: Ordering,
+    pub misses: u32,
+    pub range: RangeInclusive<u32>,
+}
+
+impl Clue {
+    pub fn distance(&self) -> u32 {
+        self.guess.abs_diff(self.secret)
+    }
+
+    // Distance as a share of the whole range, so "hot" means the same thing on easy and on hard.
+    fn closeness(&self) -> f64 {
+        let size = (self.range.end() - self.range.start()) as f64 + 1.0;
+        self.distance() as f64 / size
+    }
+}
+
+// How a missed guess is explained to the player. Strategies are picked once when the game starts.
+pub trait HintStrategy {
+    fn hint(&mut self, clue: &Clue) -> String;
+}
+
+// The original feedback of the game.
+pub struct Classic;
+
+impl HintStrategy for Classic {
+    fn hint(&mut self, clue: &Clue) -> String {
+        direction(clue.ordering).to_string()
+    }
+}
+
+fn direction(ordering: Ordering) -> &'static str {
+    match ordering {
+        Ordering::Less => "Too small",
+        Ordering::Greater => "Too big",
+        Ordering::Equal => "You win!",
+    }
+}
+
+// Only tells how close the guess is, never in which direction.
+pub struct HotCold;
+
+impl HintStrategy for HotCold {
+    fn hint(&mut self, clue: &Clue) -> String {
+        let closeness = clue.closeness();
+
+        let hint = if closeness <= 0.01 {
+            "Burning hot!"
+        } else if closeness <= 0.05 {
+            "Hot"
+        } else if closeness <= 0.15 {
+            "Warm"
+        } else if closeness <= 0.30 {
+            "Cool"
+        } else {
+            "Cold"
+        };
+
+        hint.to_string()
+    }
+}
+
+// Compares every guess with the one before it. The first guess has nothing to compare against.
+#[derive(Default)]
+pub struct WarmerColder {
+    previous_distance: Option<u32>,
+}
+
+impl HintStrategy for WarmerColder {
+    fn hint(&mut self, clue: &Clue) -> String {
+        let distance = clue.distance();
+        let previous = self.previous_distance.replace(distance);
+
+        let hint = match previous.map(|previous| distance.cmp(&previous)) {
+            None => "Missed, guess again to find out if you get warmer",
+            Some(Ordering::Less) => "Warmer",
+            Some(Ordering::Greater) => "Colder",
+            Some(Ordering::Equal) => "Same distance as before",
+        };
+
+        hint.to_string()
+    }
+}
+
+// Classic feedback plus a bonus fact about the secret after some misses.
+// A fact is unlocked once and stays told, the next one needs more misses.
+pub struct Divisibility {
+    unlocked: usize,
+}
+
+impl Divisibility {
+    // (misses needed, divisor)
+    const UNLOCKS: [(u32, u32); 3] = [(3, 2), (5, 3), (7, 5)];
+
+    pub fn new() -> Divisibility {
+        Divisibility { unlocked: 0 }
+    }
+}
+
+impl Default for Divisibility {
+    fn default() -> Divisibility {
+        Divisibility::new()
+    }
+}
+
+impl HintStrategy for Divisibility {
+    fn hint(&mut self, clue: &Clue) -> String {
+        let mut hint = direction(clue.ordering).to_string();
+
+        let Some(&(misses, divisor)) = Divisibility::UNLOCKS.get(self.unlocked) else {
+            return hint;
+        };
+
+        if clue.misses >= misses {
+            self.unlocked += 1;
+
+            let fact = match (divisor, clue.secret.is_multiple_of(divisor)) {
+                (2, true) => String::from("The number is even."),
+                (2, false) => String::from("The number is odd."),
+                (_, true) => format!("The number is divisible by {divisor}."),
+                (_, false) => format!("The number is not divisible by {divisor}."),
+            };
+
+            hint = format!("{hint}\nHint unlocked: {fact}");
+        }
+
+        hint
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HintMode {
+    Classic,
+    HotCold,
+    WarmerColder,
+    Divisibility,
+}
+
+impl HintMode {
+    // Whether the player learns if a guess was too small or too big.
+    pub fn reveals_direction(&self) -> bool {
+        matches!(self, HintMode::Classic | HintMode::Divisibility)
+    }
+
+    pub fn build(&self) -> Box<dyn HintStrategy> {
+        match self {
+            HintMode::Classic => Box::new(Classic),
+            HintMode::HotCold => Box::new(HotCold),
+            HintMode::WarmerColder => Box::<WarmerColder>::default(),
+            HintMode::Divisibility => Box::new(Divisibility::new()),
+        }
+    }
+}
+
+impl fmt::Display for HintMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            HintMode::Classic => "classic",
+            HintMode::HotCold => "hot-cold",
+            HintMode::WarmerColder => "warmer-colder",
+            HintMode::Divisibility => "divisibility",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for HintMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<HintMode, String> {
+        match name.trim().to_lowercase().as_str() {
+            "classic" => Ok(HintMode::Classic),
+            "hot-cold" | "hotcold" => Ok(HintMode::HotCold),
+            "warmer-colder" | "warmercolder" => Ok(HintMode::WarmerColder),
+            "divisibility" | "parity" => Ok(HintMode::Divisibility),
+            _ => Err(format!(
+                "unknown hint mode `{name}`, expected classic, hot-cold, warmer-colder or divisibility"
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn clue(guess: u32, secret: u32, misses: u32) -> Clue {
+        Clue {
+            guess,
+            secret,
+            ordering: guess.cmp(&secret),
+            misses,
+            range: 1..=100,
+        }
+    }
+
+    #[test]
+    fn classic_is_direction() {
+        assert_eq!(Classic.hint(&clue(10, 50, 1)), "Too small");
+        assert_eq!(Classic.hint(&clue(90, 50, 1)), "Too big");
+    }
+
+    #[test]
+    fn hot_cold_uses_distance() {
+        assert_eq!(HotCold.hint(&clue(49, 50, 1)), "Burning hot!");
+        assert_eq!(HotCold.hint(&clue(60, 50, 1)), "Warm");
+        assert_eq!(HotCold.hint(&clue(1, 100, 1)), "Cold");
+    }
+
+    #[test]
+    fn warmer_colder_compares_with_previous_guess() {
+        let mut strategy = WarmerColder::default();
+
+        assert!(strategy.hint(&clue(10, 50, 1)).starts_with("Missed"));
+        assert_eq!(strategy.hint(&clue(80, 50, 2)), "Warmer");
+        assert_eq!(strategy.hint(&clue(20, 50, 3)), "Same distance as before");
+        assert_eq!(strategy.hint(&clue(1, 50, 4)), "Colder");
+    }
+
+    #[test]
+    fn divisibility_unlocks_after_misses() {
+        let mut strategy = Divisibility::new();
+
+        assert_eq!(strategy.hint(&clue(10, 30, 1)), "Too small");
+        assert_eq!(strategy.hint(&clue(11, 30, 2)), "Too small");
+        assert_eq!(
+            strategy.hint(&clue(12, 30, 3)),
+            "Too small\nHint unlocked: The number is even."
+        );
+        assert_eq!(strategy.hint(&clue(13, 30, 4)), "Too small");
+        assert!(strategy
+            .hint(&clue(14, 30, 5))
+            .ends_with("The number is divisible by 3."));
+    }
+
+    #[test]
+    fn mode_names() {
+        assert_eq!("hot-cold".parse(), Ok(HintMode::HotCold));
+        assert_eq!(HintMode::WarmerColder.to_string(), "warmer-colder");
+        assert!("loud".parse::<HintMode>().is_err());
+    }
+}
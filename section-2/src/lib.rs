@@ -0,0 +1,25 @@
+// Rules of the guessing game live in this library crate so they can be tested without stdin.
+// `main.rs` is only a thin shell that reads guesses and prints what the engine answers.
+pub mod bot;
+pub mod config;
+pub mod difficulty;
+pub mod game;
+pub mod hints;
+pub mod net;
+pub mod player;
+pub mod reverse;
+pub mod scoreboard;
+pub mod simulation;
+pub mod transcript;
+pub mod tui;
+pub mod view;
+pub mod word;
+
+pub use bot::BotKind;
+pub use config::{Config, Mode};
+pub use difficulty::{Difficulty, Settings, SettingsError};
+pub use game::{GameSession, GuessError, GuessResult, Status, Summary};
+pub use hints::{HintMode, HintStrategy};
+pub use player::{Guesser, HumanGuesser};
+pub use scoreboard::{Score, Scoreboard};
+pub use view::{LineView, View};
@@ -1,62 +1,409 @@
-use std::io; // Here we say we will be using standart library input/output
-use rand::Rng;
+use std::cell::RefCell;
 use std::cmp::Ordering;
+use std::io; // Here we say we will be using standart library input/output
+use std::net::{TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::time::Instant;
+use std::{env, process};
+
+use guessing_game::net::{self, Message};
+use guessing_game::reverse::{Answer, ReverseGame};
+use guessing_game::transcript::{self, Event, Recorder};
+use guessing_game::tui::TuiView;
+use guessing_game::word::{self, Word, WordGame, WordList, MAX_WORD_ATTEMPTS, WORD_LENGTH};
+use guessing_game::{
+    player, scoreboard, simulation, BotKind, Config, GameSession, Guesser, HumanGuesser, LineView,
+    Mode, Score, Scoreboard, Status, View,
+};
 
 fn main() {
-    println!("Guess the number!");
-
-    // loop will run it's scope until it matches a break
-    loop {
-        // firstly we are calling `thread_rng` function and it returns a number generater
-        // secondly we call `gen_range` to generate number, this function takes a range expression
-        // `start..=end` is a range expression and it includes both start and end values
-        let generated_number = rand::thread_rng().gen_range(1..=100);
-
-        // `String::new()` will return new instance of String.
-        // :: syntax indicates that new is an associated function. It is a function that's implemented on a type.
-        let mut guess = String::new();
-
-        // We are using standart in function and calling readline function of it.
-        // `read_line` functin takes a parameter and it is about on which string it should store user entered text.
-        // note: `read_line` function appends entered text to given string and not overwrite its contents.
-        // `read_line` function takes a string reference and that means it takes a reference of variable's placement on memory
-        // and saying `&mut` means that we pass mutable reference of guess variable.
-        // Potential Failure Handling: on second line function `read_line` returns Result value. This Result value is an enumeration (enum)
-        // by calling `expect` function we are saying if result of `read_line` is Err it should print this message and crash the program
-        // If we comment `expect` function program will compile but still it will give us a warning about possible danger of not handling error.
-        io::stdin()
-            .read_line(&mut guess)
-            .expect("Failed to read line");
-
-        // We already have a variable named guess but rust allows us to shadow the previous value with new one.
-        // This is called `Shadowing`
-        // here first we trim whitespaces on both and end of the original guess variable
-        // later we tell rust to parse it's type and type of it should be `u32` because it is the type annotation we give
-        // we add a error handling on situations where parsing might fail. (example: entering a char)
-        // let guess: u32 = guess.trim().parse().expect("Please type a number");
-
-        // this is another way of handling Result values with pattern matching
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num, // this will run if Result is handled without an error
-            Err(_) => {
-                println!("Invalid value is given. Guess again!");
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    match config.mode {
+        Mode::Play => play(&config),
+        Mode::Leaderboard { top } => print_leaderboard(&config, top),
+        Mode::Replay { ref transcript } => replay(transcript),
+        Mode::Simulate { games } => simulate(&config, games),
+        Mode::Host {
+            ref address,
+            players,
+        } => host(&config, address, players),
+        Mode::Join { ref address } => join(&config, address),
+        Mode::Words {
+            ref word_list,
+            daily,
+        } => play_words(&config, word_list, daily),
+        Mode::Reverse => play_reverse(&config),
+    }
+}
+
+fn play(config: &Config) {
+    let settings = &config.settings;
+
+    // The view decides how the game is drawn, the loop below only tells it what happened.
+    // It is shared with the human player, whose prompts have to go through it as well.
+    let view: Rc<RefCell<dyn View>> = if config.tui {
+        Rc::new(RefCell::new(TuiView::new(config.hints.reveals_direction())))
+    } else {
+        Rc::new(RefCell::new(LineView))
+    };
+
+    // Every game has a seed, a random one unless `--seed` was given, so any game can be played again.
+    let seed = config.seed.unwrap_or_else(rand::random);
+
+    // The secret is drawn once here, `GameSession` keeps it until the round is over.
+    let mut session = GameSession::with_seed(seed, settings.range.clone(), settings.max_attempts);
+    let started = Instant::now();
+
+    let mut recorder = config.transcript.as_ref().map(|path| {
+        Recorder::create(path).unwrap_or_else(|err| {
+            eprintln!("Could not create transcript {}: {err}", path.display());
+            process::exit(1);
+        })
+    });
+    record(&mut recorder, &Event::start(seed, settings));
+
+    // Guesses come either from a bot or from a person typing on stdin, the loop below does not care which one.
+    let mut guesser: Box<dyn Guesser> = match config.bot {
+        Some(bot) => {
+            println!("The {bot} bot is playing.");
+            bot.build(settings.range.clone(), seed)
+        }
+        None => {
+            let view = Rc::clone(&view);
+            Box::new(
+                HumanGuesser::new(io::stdin().lock(), settings.range.clone())
+                    .with_prompts(move |text| view.borrow_mut().prompt(text)),
+            )
+        }
+    };
+
+    view.borrow_mut().start(settings);
+
+    // The hint strategy decides how a missed guess is explained, e.g. "Too small" or "Warmer".
+    let mut hints = config.hints.build();
+
+    // `while let` will run it's scope as long as guesser gives a guess, `None` means stdin was closed
+    while let Some(guess) = guesser.next_guess() {
+        let result = match session.guess(guess) {
+            Ok(result) => result,
+            Err(error) => {
+                view.borrow_mut().refused(guess, &error);
                 continue;
-            }, // this will run if Result has an error, here it will skip current loop and start from start
+            }
         };
 
-        // We can use variables inside strings using curly brackets. Anything between curly brackets will be printed.
-        println!("You guessed: {guess}");
+        record(&mut recorder, &Event::guess(guess, &result));
+        guesser.feedback(guess, result.ordering);
+
+        let hint = match result.ordering {
+            Ordering::Equal => String::from("You win!"), // these are called arms of the enum
+            ordering => hints.hint(&session.clue(guess, ordering)),
+        };
+
+        view.borrow_mut().guessed(guess, result.ordering, &hint, &session);
+
+        if session.is_over() {
+            break; // this will break loop
+        }
+    }
+
+    if !session.is_over() {
+        return; // game was abandoned, nothing to record
+    }
+
+    view.borrow_mut().finished(&session.summary());
+    println!("Game seed was {seed}.");
+
+    record(&mut recorder, &Event::end(&session.summary()));
 
-        match guess.cmp(&generated_number) {
-            Ordering::Less => println!("Too small"), // these are called arms of the enum
-            Ordering::Greater => println!("Too big"),
-            Ordering::Equal => {
-                println!("You win!");
-                break; // this will break loop 
-            },
+    // only games played by people go to the scoreboard
+    if config.bot.is_some() {
+        return;
+    }
+
+    let player = config.player.clone().unwrap_or_else(ask_player_name);
+    let score = Score::from_session(&player, &session, settings.difficulty, started.elapsed());
+
+    // Not being able to save the score should not crash an already finished game.
+    if let Err(error) = Scoreboard::append(&config.scoreboard, &score) {
+        println!("Could not save your score: {error}");
+    }
+}
+
+fn play_words(config: &Config, word_list: &Option<PathBuf>, daily: bool) {
+    let list = match word_list {
+        Some(path) => WordList::load(path).unwrap_or_else(|err| {
+            eprintln!("Could not read word list {}: {err}", path.display());
+            process::exit(1);
+        }),
+        None => WordList::built_in(),
+    };
+
+    // The daily word uses the day number as seed, so everyone gets the same word on the same day.
+    let (seed, title) = if daily {
+        let day = word::today();
+        (day, format!("Word game {day}"))
+    } else {
+        (config.seed.unwrap_or_else(rand::random), String::from("Word game"))
+    };
+
+    let mut game = WordGame::new(list.pick(seed));
+
+    println!("Guess the {WORD_LENGTH} letter word! You have {MAX_WORD_ATTEMPTS} attempts.");
+    println!("🟩 right place, 🟨 wrong place, ⬛ not in the word.");
+
+    let mut input = io::stdin().lock();
+
+    while let Some(guess) = player::read_parsed::<Word>(
+        &mut input,
+        &format!("Please input a {WORD_LENGTH} letter word:"),
+        &format!("Invalid value is given, expected {WORD_LENGTH} letters. Guess again!"),
+    ) {
+        match game.guess(&guess, &list) {
+            Ok(letters) => {
+                let row: String = letters.iter().map(|letter| letter.emoji()).collect();
+                println!("{row} {}", guess.as_str().to_uppercase());
+            }
+            Err(error) => {
+                println!("{error}");
+                continue;
+            }
+        }
+
+        if game.status() != Status::Playing {
+            break;
         }
+    }
+
+    match game.status() {
+        Status::Playing => return, // stdin was closed before the end
+        Status::Won => println!("You found the word in {} tries.", game.attempts()),
+        Status::Lost => println!("Out of attempts! The word was {}.", game.secret()),
+    }
+
+    if !daily {
+        println!("Game seed was {seed}.");
+    }
+
+    println!();
+    println!("{}", game.share(&title));
+}
+
+fn play_reverse(config: &Config) {
+    let settings = &config.settings;
+    let mut game = ReverseGame::new(settings.range.clone());
+
+    println!(
+        "Think of a number between {} and {}, I will guess it!",
+        settings.min(),
+        settings.max()
+    );
+
+    let mut input = io::stdin().lock();
+
+    while game.found().is_none() {
+        let guess = game.guess();
+
+        let Some(answer) = player::read_parsed::<Answer>(
+            &mut input,
+            &format!("Is your number {guess}? Answer higher, lower or correct (h/l/c):"),
+            "Invalid value is given, expected higher, lower or correct. Answer again!",
+        ) else {
+            return; // stdin was closed before the number was found
+        };
 
-        println!("Generated number was: {generated_number}");
+        // A contradicting answer is not taken, the same guess is asked again after the explanation.
+        if let Err(contradiction) = game.answer(answer) {
+            println!("{contradiction}");
+            println!("One of your answers must have been a mistake, let's try {guess} again.");
+        }
+    }
+
+    if let Some(number) = game.found() {
+        println!("Your number is {number}! I found it in {} tries.", game.guesses());
+    }
+}
+
+// A transcript that cannot be written should not stop the game, recording is turned off instead.
+fn record<W: io::Write>(recorder: &mut Option<Recorder<W>>, event: &Event) {
+    if let Some(writer) = recorder {
+        if let Err(error) = writer.record(event) {
+            println!("Could not write transcript, recording stopped: {error}");
+            *recorder = None;
+        }
+    }
+}
+
+fn replay(path: &Path) {
+    let events = transcript::load(path).unwrap_or_else(|err| {
+        eprintln!("Could not read {}: {err}", path.display());
+        process::exit(1);
+    });
+
+    match transcript::replay(&events) {
+        Ok(summary) => {
+            println!("Replay matches the transcript.");
+            println!("{summary}");
+        }
+        Err(error) => {
+            eprintln!("Replay does not match the transcript: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn simulate(config: &Config, games: usize) {
+    let bot = config.bot.unwrap_or(BotKind::Binary);
+    let seed = config.seed.unwrap_or_else(rand::random);
+    let settings = &config.settings;
+
+    println!(
+        "Simulating {games} games of the {bot} bot between {} and {} (seed {seed})",
+        settings.min(),
+        settings.max()
+    );
+    print!("{}", simulation::simulate(games, bot, settings, seed));
+}
+
+fn host(config: &Config, address: &str, players: usize) {
+    let settings = &config.settings;
+    let seed = config.seed.unwrap_or_else(rand::random);
+
+    let listener = TcpListener::bind(address).unwrap_or_else(|err| {
+        eprintln!("Could not listen on {address}: {err}");
+        process::exit(1);
+    });
+
+    println!(
+        "Hosting a game between {} and {} on {address}, waiting for {players} players.",
+        settings.min(),
+        settings.max()
+    );
+
+    let session = GameSession::with_seed(seed, settings.range.clone(), settings.max_attempts);
+
+    match net::host(&listener, players, session, net::TURN_TIMEOUT, |line| {
+        println!("{line}")
+    }) {
+        Ok(Some(winner)) => println!("{winner} won the game!"),
+        Ok(None) => println!("Nobody won this time."),
+        Err(error) => {
+            eprintln!("Hosting failed: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn join(config: &Config, address: &str) {
+    let stream = TcpStream::connect(address).unwrap_or_else(|err| {
+        eprintln!("Could not connect to {address}: {err}");
+        process::exit(1);
+    });
+
+    let name = config.player.clone().unwrap_or_else(ask_player_name);
+    let bot = config.bot;
+
+    let make_guesser = |range: RangeInclusive<u32>| -> Box<dyn Guesser> {
+        match bot {
+            Some(bot) => bot.build(range, rand::random()),
+            None => Box::new(HumanGuesser::new(io::stdin().lock(), range)),
+        }
+    };
+
+    // the host tells us the name it knows us by in the welcome message
+    let mut me = String::new();
+
+    let show = |message: &Message| match message {
+        Message::Welcome { name, min, max } => {
+            me = name.clone();
+            println!("Joined as {me}! The number is between {min} and {max}.");
+        }
+        Message::Joined(player) => println!("{player} joined the game."),
+        Message::Turn(player) if *player != me => println!("Waiting for {player}..."),
+        Message::YourTurn => println!("Your turn!"),
+        Message::Result {
+            player,
+            guess,
+            ordering,
+        } => {
+            let hint = match ordering {
+                Ordering::Less => "too small",
+                Ordering::Greater => "too big",
+                Ordering::Equal => "correct",
+            };
+            println!("{player} guessed {guess}: {hint}");
+        }
+        Message::Error(reason) => println!("{reason}"),
+        Message::Left(player) => println!("{player} left the game."),
+        Message::Winner {
+            player,
+            secret,
+            attempts,
+        } => println!("{player} won! The number was {secret}, found after {attempts} guesses in total."),
+        Message::GameOver { secret } => println!("Out of attempts! The number was {secret}."),
+        _ => {}
+    };
+
+    match net::join(stream, &name, make_guesser, show) {
+        Ok(Some(_)) => {}
+        Ok(None) => println!("Left the game."),
+        Err(error) => {
+            eprintln!("Connection to the host failed: {error}");
+            process::exit(1);
+        }
+    }
+}
+
+fn ask_player_name() -> String {
+    println!("Enter your name for the scoreboard:");
+
+    let mut name = String::new();
+
+    match io::stdin().read_line(&mut name) {
+        Ok(_) if !name.trim().is_empty() => name.trim().to_string(),
+        _ => String::from("anonymous"),
+    }
+}
+
+fn print_leaderboard(config: &Config, top: usize) {
+    let scoreboard = match Scoreboard::load(&config.scoreboard) {
+        Ok(scoreboard) => scoreboard,
+        Err(error) => {
+            eprintln!("Could not read {}: {error}", config.scoreboard.display());
+            process::exit(1);
+        }
+    };
+
+    if scoreboard.skipped_lines > 0 {
+        println!("(skipped {} unreadable lines)", scoreboard.skipped_lines);
+    }
+
+    let groups = scoreboard.top(top);
+
+    if groups.is_empty() {
+        println!("No games won yet.");
+        return;
+    }
+
+    for (group, scores) in groups {
+        println!("{group}");
+
+        for (place, score) in scores.iter().enumerate() {
+            println!(
+                "{:>3}. {:<16} {:>4} tries {:>8.1}s  {}",
+                place + 1,
+                score.player,
+                score.attempts,
+                score.duration.as_secs_f64(),
+                scoreboard::format_date(score.finished_at)
+            );
+        }
     }
 }
 
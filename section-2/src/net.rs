@@ -0,0 +1,611 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::game::{GameSession, Status};
+use crate::player::Guesser;
+
+// Host and players talk with one text line per message, e.g. `GUESS 50` or `RESULT alice 50 less`.
+// Player names never contain spaces, so every field can be split on whitespace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    // player -> host
+    Name(String),
+    Guess(u32),
+    // host -> player
+    // tells the player the name the host knows them by, it can differ from the one they asked for
+    Welcome {
+        name: String,
+        min: u32,
+        max: u32,
+    },
+    Joined(String),
+    Turn(String),
+    YourTurn,
+    Result {
+        player: String,
+        guess: u32,
+        ordering: Ordering,
+    },
+    Error(String),
+    Left(String),
+    Winner {
+        player: String,
+        secret: u32,
+        attempts: u32,
+    },
+    GameOver {
+        secret: u32,
+    },
+}
+
+impl fmt::Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Message::Name(name) => write!(f, "NAME {name}"),
+            Message::Guess(guess) => write!(f, "GUESS {guess}"),
+            Message::Welcome { name, min, max } => write!(f, "WELCOME {name} {min} {max}"),
+            Message::Joined(name) => write!(f, "JOINED {name}"),
+            Message::Turn(name) => write!(f, "TURN {name}"),
+            Message::YourTurn => write!(f, "YOUR_TURN"),
+            Message::Result {
+                player,
+                guess,
+                ordering,
+            } => {
+                let ordering = match ordering {
+                    Ordering::Less => "less",
+                    Ordering::Greater => "greater",
+                    Ordering::Equal => "equal",
+                };
+                write!(f, "RESULT {player} {guess} {ordering}")
+            }
+            Message::Error(reason) => write!(f, "ERROR {reason}"),
+            Message::Left(name) => write!(f, "LEFT {name}"),
+            Message::Winner {
+                player,
+                secret,
+                attempts,
+            } => write!(f, "WINNER {player} {secret} {attempts}"),
+            Message::GameOver { secret } => write!(f, "GAME_OVER {secret}"),
+        }
+    }
+}
+
+impl FromStr for Message {
+    type Err = String;
+
+    fn from_str(line: &str) -> Result<Message, String> {
+        let line = line.trim();
+        let (kind, rest) = line.split_once(' ').unwrap_or((line, ""));
+        let fields: Vec<&str> = rest.split_whitespace().collect();
+
+        fn number(value: Option<&&str>) -> Result<u32, String> {
+            let value = value.ok_or("missing number")?;
+            value
+                .parse()
+                .map_err(|_| format!("`{value}` is not a number"))
+        }
+
+        fn text(value: Option<&&str>) -> Result<String, String> {
+            value
+                .map(|value| value.to_string())
+                .ok_or_else(|| String::from("missing name"))
+        }
+
+        let message = match kind {
+            // names are cleaned up by the host, so everything after `NAME` is taken as it is
+            "NAME" => Message::Name(rest.trim().to_string()),
+            "GUESS" => Message::Guess(number(fields.first())?),
+            "WELCOME" => Message::Welcome {
+                name: text(fields.first())?,
+                min: number(fields.get(1))?,
+                max: number(fields.get(2))?,
+            },
+            "JOINED" => Message::Joined(text(fields.first())?),
+            "TURN" => Message::Turn(text(fields.first())?),
+            "YOUR_TURN" => Message::YourTurn,
+            "RESULT" => Message::Result {
+                player: text(fields.first())?,
+                guess: number(fields.get(1))?,
+                ordering: match fields.get(2) {
+                    Some(&"less") => Ordering::Less,
+                    Some(&"greater") => Ordering::Greater,
+                    Some(&"equal") => Ordering::Equal,
+                    _ => return Err(format!("bad result `{line}`")),
+                },
+            },
+            "ERROR" => Message::Error(rest.to_string()),
+            "LEFT" => Message::Left(text(fields.first())?),
+            "WINNER" => Message::Winner {
+                player: text(fields.first())?,
+                secret: number(fields.get(1))?,
+                attempts: number(fields.get(2))?,
+            },
+            "GAME_OVER" => Message::GameOver {
+                secret: number(fields.first())?,
+            },
+            _ => return Err(format!("unknown message `{line}`")),
+        };
+
+        Ok(message)
+    }
+}
+
+// How long the host waits for a player's guess before giving up on them.
+// Humans are at the other end too, so this is generous.
+pub const TURN_TIMEOUT: Duration = Duration::from_secs(120);
+
+// The name is sent by the client program right after connecting, nobody has to type it.
+// Kept short because the lobby waits for nothing else while it reads it.
+pub const NAME_TIMEOUT: Duration = Duration::from_secs(5);
+
+// Reads one message, `Ok(None)` means the other side closed the connection.
+fn receive(reader: &mut impl BufRead) -> io::Result<Option<Message>> {
+    loop {
+        let mut line = String::new();
+
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        return line
+            .parse()
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err));
+    }
+}
+
+fn send(writer: &mut impl Write, message: &Message) -> io::Result<()> {
+    writeln!(writer, "{message}")?;
+    writer.flush()
+}
+
+// A connected player as the host sees it.
+struct Peer {
+    name: String,
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+    connected: bool,
+}
+
+impl Peer {
+    fn send(&mut self, message: &Message) -> bool {
+        if self.connected && send(&mut self.writer, message).is_err() {
+            self.connected = false;
+        }
+
+        self.connected
+    }
+}
+
+// Sends `message` to every connected player. Anyone who cannot be reached is marked as gone
+// and the others are told about it, which can of course find more players that are gone.
+fn announce(players: &mut [Peer], message: &Message, log: &mut impl FnMut(&str)) {
+    let mut pending = vec![message.clone()];
+
+    while let Some(message) = pending.pop() {
+        for player in players.iter_mut().filter(|player| player.connected) {
+            if !player.send(&message) {
+                log(&format!("{} disconnected", player.name));
+                pending.push(Message::Left(player.name.clone()));
+            }
+        }
+    }
+}
+
+// Player names end up in space separated messages, so spaces are replaced and duplicates get a number.
+fn unique_name(name: &str, players: &[Peer]) -> String {
+    let base: String = name.split_whitespace().collect::<Vec<&str>>().join("_");
+    let base = if base.is_empty() {
+        String::from("player")
+    } else {
+        base
+    };
+
+    let mut name = base.clone();
+    let mut number = 2;
+
+    while players.iter().any(|player| player.name == name) {
+        name = format!("{base}#{number}");
+        number += 1;
+    }
+
+    name
+}
+
+// A read that ran into the timeout set on the stream. Which kind it shows up as depends on the platform.
+fn timed_out(error: &io::Error) -> bool {
+    matches!(
+        error.kind(),
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
+// Waits for `expected` players, then lets them guess in turns against `session` until someone wins,
+// the attempts run out or everybody leaves. Returns the name of the winner.
+// A player who says nothing for `timeout` is dropped, so one silent client cannot stall the game.
+// Connections that fail or do not send a name in time are logged and skipped, the lobby keeps waiting.
+pub fn host(
+    listener: &TcpListener,
+    expected: usize,
+    mut session: GameSession,
+    timeout: Duration,
+    mut log: impl FnMut(&str),
+) -> io::Result<Option<String>> {
+    let mut players: Vec<Peer> = Vec::new();
+
+    while players.len() < expected {
+        let (stream, address) = match listener.accept() {
+            Ok(connection) => connection,
+            Err(err) => {
+                log(&format!("could not accept a connection: {err}"));
+                continue;
+            }
+        };
+
+        let reader = stream
+            .set_read_timeout(Some(timeout.min(NAME_TIMEOUT)))
+            .and_then(|()| stream.try_clone());
+        let mut reader = match reader {
+            Ok(reader) => BufReader::new(reader),
+            Err(err) => {
+                log(&format!("could not set up {address}: {err}"));
+                continue;
+            }
+        };
+
+        // the first line of a new connection must tell the name of the player
+        let name = match receive(&mut reader) {
+            Ok(Some(Message::Name(name))) => unique_name(&name, &players),
+            _ => {
+                log(&format!("{address} did not introduce itself, dropping it"));
+                continue;
+            }
+        };
+
+        // from here on the player gets the whole turn to answer, the timeout belongs to the socket
+        if let Err(err) = stream.set_read_timeout(Some(timeout)) {
+            log(&format!("could not set up {address}: {err}"));
+            continue;
+        }
+
+        let mut peer = Peer {
+            name: name.clone(),
+            reader,
+            writer: stream,
+            connected: true,
+        };
+
+        let range = session.range();
+        if !peer.send(&Message::Welcome {
+            name: name.clone(),
+            min: *range.start(),
+            max: *range.end(),
+        }) {
+            continue;
+        }
+
+        log(&format!("{name} joined from {address}"));
+        players.push(peer);
+        announce(&mut players, &Message::Joined(name), &mut log);
+    }
+
+    let mut turn = 0;
+
+    loop {
+        players.retain(|player| player.connected);
+
+        if players.is_empty() {
+            log("Everybody left, game is over.");
+            return Ok(None);
+        }
+
+        turn %= players.len();
+        let name = players[turn].name.clone();
+
+        announce(&mut players, &Message::Turn(name.clone()), &mut log);
+
+        let player = &mut players[turn];
+
+        if !player.send(&Message::YourTurn) {
+            continue;
+        }
+
+        let guess = match receive(&mut player.reader) {
+            Ok(Some(Message::Guess(guess))) => guess,
+            Ok(Some(other)) => {
+                player.send(&Message::Error(format!("expected a guess, got `{other}`")));
+                continue;
+            }
+            // closed connections, broken lines and silence are all treated as the player leaving
+            received @ (Ok(None) | Err(_)) => {
+                let reason = match received {
+                    Err(error) if timed_out(&error) => "timed out",
+                    _ => "disconnected",
+                };
+                player.connected = false;
+                log(&format!("{name} {reason}"));
+                announce(&mut players, &Message::Left(name), &mut log);
+                continue;
+            }
+        };
+
+        let result = match session.guess(guess) {
+            Ok(result) => result,
+            Err(error) => {
+                // same player tries again
+                player.send(&Message::Error(error.to_string()));
+                continue;
+            }
+        };
+
+        log(&format!("{name} guessed {guess}: {:?}", result.ordering));
+        announce(
+            &mut players,
+            &Message::Result {
+                player: name.clone(),
+                guess,
+                ordering: result.ordering,
+            },
+            &mut log,
+        );
+
+        let summary = session.summary();
+
+        match result.status {
+            Status::Won => {
+                announce(
+                    &mut players,
+                    &Message::Winner {
+                        player: name.clone(),
+                        secret: summary.secret,
+                        attempts: summary.attempts,
+                    },
+                    &mut log,
+                );
+                return Ok(Some(name));
+            }
+            Status::Lost => {
+                announce(
+                    &mut players,
+                    &Message::GameOver {
+                        secret: summary.secret,
+                    },
+                    &mut log,
+                );
+                return Ok(None);
+            }
+            Status::Playing => turn += 1,
+        }
+    }
+}
+
+// Joins a hosted game and plays it with the guesser `make_guesser` builds once the range is known.
+// Every message from the host is passed to `show`. Returns the final `Winner` or `GameOver` message,
+// `None` when the connection was closed before the game ended or the guesser gave up.
+pub fn join(
+    stream: TcpStream,
+    name: &str,
+    make_guesser: impl FnOnce(RangeInclusive<u32>) -> Box<dyn Guesser>,
+    mut show: impl FnMut(&Message),
+) -> io::Result<Option<Message>> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut writer = stream;
+
+    send(&mut writer, &Message::Name(name.to_string()))?;
+
+    let mut guesser = match receive(&mut reader)? {
+        Some(message @ Message::Welcome { min, max, .. }) => {
+            show(&message);
+            make_guesser(min..=max)
+        }
+        Some(other) => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected a welcome, got `{other}`"),
+            ))
+        }
+        None => return Ok(None),
+    };
+
+    while let Some(message) = receive(&mut reader)? {
+        show(&message);
+
+        match message {
+            Message::YourTurn => match guesser.next_guess() {
+                Some(guess) => send(&mut writer, &Message::Guess(guess))?,
+                None => return Ok(None),
+            },
+            // everyone's guesses are useful, not only our own
+            Message::Result {
+                guess, ordering, ..
+            } => guesser.feedback(guess, ordering),
+            Message::Winner { .. } | Message::GameOver { .. } => return Ok(Some(message)),
+            _ => {}
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bot::BinaryBot;
+    use std::thread;
+
+    fn listener() -> (TcpListener, String) {
+        // port 0 lets the operating system pick a free port on loopback
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        (listener, address)
+    }
+
+    fn bot_player(address: String, name: &'static str) -> thread::JoinHandle<Option<Message>> {
+        thread::spawn(move || {
+            let stream = TcpStream::connect(address).unwrap();
+            join(
+                stream,
+                name,
+                |range| Box::new(BinaryBot::new(range)),
+                |_| {},
+            )
+            .unwrap()
+        })
+    }
+
+    #[test]
+    fn messages_round_trip() {
+        let messages = [
+            Message::Name(String::from("big al")),
+            Message::Guess(42),
+            Message::Welcome {
+                name: String::from("alice#2"),
+                min: 1,
+                max: 100,
+            },
+            Message::YourTurn,
+            Message::Result {
+                player: String::from("bob"),
+                guess: 7,
+                ordering: Ordering::Greater,
+            },
+            Message::Error(String::from("7 is not between 10 and 20")),
+            Message::Winner {
+                player: String::from("bob"),
+                secret: 3,
+                attempts: 4,
+            },
+            Message::GameOver { secret: 9 },
+        ];
+
+        for message in messages {
+            assert_eq!(message.to_string().parse(), Ok(message));
+        }
+
+        assert!("GUESS many".parse::<Message>().is_err());
+        assert!("HELLO".parse::<Message>().is_err());
+    }
+
+    #[test]
+    fn two_players_take_turns_until_someone_wins() {
+        let (listener, address) = listener();
+
+        let alice = bot_player(address.clone(), "alice");
+        let bob = bot_player(address, "bob");
+
+        let session = GameSession::with_secret(77, 1..=100, None);
+        let winner = host(&listener, 2, session, TURN_TIMEOUT, |_| {}).unwrap();
+
+        let alice = alice.join().unwrap();
+        let bob = bob.join().unwrap();
+
+        // both players see the same final announcement
+        assert_eq!(alice, bob);
+        match alice {
+            Some(Message::Winner { player, secret, .. }) => {
+                assert_eq!(Some(player), winner);
+                assert_eq!(secret, 77);
+            }
+            other => panic!("expected a winner, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn disconnected_player_is_skipped() {
+        let (listener, address) = listener();
+
+        // joins and leaves as soon as it is its turn
+        let quitter = {
+            let address = address.clone();
+            thread::spawn(move || {
+                let stream = TcpStream::connect(address).unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut writer = stream;
+                send(&mut writer, &Message::Name(String::from("quitter"))).unwrap();
+
+                while let Ok(Some(message)) = receive(&mut reader) {
+                    if message == Message::YourTurn {
+                        break;
+                    }
+                }
+            })
+        };
+        let stayer = bot_player(address, "stayer");
+
+        let session = GameSession::with_secret(30, 1..=100, None);
+        let mut log = Vec::new();
+        let winner = host(&listener, 2, session, TURN_TIMEOUT, |line| {
+            log.push(line.to_string())
+        })
+        .unwrap();
+
+        quitter.join().unwrap();
+
+        assert_eq!(winner, Some(String::from("stayer")));
+        assert!(log.iter().any(|line| line == "quitter disconnected"));
+        assert!(matches!(
+            stayer.join().unwrap(),
+            Some(Message::Winner { .. })
+        ));
+    }
+
+    #[test]
+    fn silent_players_are_dropped() {
+        let (listener, address) = listener();
+
+        // connects but never says its name, it is still open when the host gives up on it
+        let mute = TcpStream::connect(&address).unwrap();
+
+        // joins properly and then never answers its turn
+        let sleeper = {
+            let address = address.clone();
+            thread::spawn(move || {
+                let stream = TcpStream::connect(address).unwrap();
+                stream.set_read_timeout(Some(TURN_TIMEOUT)).unwrap();
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut writer = stream;
+                send(&mut writer, &Message::Name(String::from("sleeper"))).unwrap();
+
+                // keeps reading without ever guessing until the host hangs up
+                while let Ok(Some(_)) = receive(&mut reader) {}
+            })
+        };
+        let stayer = bot_player(address, "stayer");
+
+        let session = GameSession::with_secret(30, 1..=100, None);
+        let mut log = Vec::new();
+        let winner = host(&listener, 2, session, Duration::from_millis(200), |line| {
+            log.push(line.to_string())
+        })
+        .unwrap();
+
+        sleeper.join().unwrap();
+        drop(mute);
+
+        assert_eq!(winner, Some(String::from("stayer")));
+        assert!(log
+            .iter()
+            .any(|line| line.ends_with("did not introduce itself, dropping it")));
+        assert!(log.iter().any(|line| line == "sleeper timed out"));
+        assert!(matches!(
+            stayer.join().unwrap(),
+            Some(Message::Winner { .. })
+        ));
+    }
+
+    #[test]
+    fn names_have_no_spaces() {
+        assert_eq!(unique_name("big al", &[]), "big_al");
+        assert_eq!(unique_name("  ", &[]), "player");
+    }
+}
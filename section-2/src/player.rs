@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::io::BufRead;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// Anything that can play the game: a person typing on stdin or one of the bots.
+// The game loop only asks for the next guess and tells how it compared against the secret,
+// so the same loop works for every kind of player.
+pub trait Guesser {
+    // `None` means the player has nothing more to guess, e.g. stdin was closed.
+    fn next_guess(&mut self) -> Option<u32>;
+
+    // Called after every accepted guess with the same "Too small"/"Too big" feedback a person sees.
+    fn feedback(&mut self, _guess: u32, _ordering: Ordering) {}
+}
+
+// A person typing guesses, one per line. In the binary `input` is `io::stdin().lock()`,
+// tests can give it any other `BufRead` such as a byte slice.
+// Prompts are printed as lines unless `with_prompts` hands them to something else, e.g. the full-screen view.
+pub struct HumanGuesser<R: BufRead> {
+    input: R,
+    range: RangeInclusive<u32>,
+    ask: Box<dyn FnMut(&str)>,
+}
+
+impl<R: BufRead> HumanGuesser<R> {
+    pub fn new(input: R, range: RangeInclusive<u32>) -> HumanGuesser<R> {
+        HumanGuesser {
+            input,
+            range,
+            ask: Box::new(|text| println!("{text}")),
+        }
+    }
+
+    pub fn with_prompts(mut self, ask: impl FnMut(&str) + 'static) -> HumanGuesser<R> {
+        self.ask = Box::new(ask);
+        self
+    }
+}
+
+impl<R: BufRead> Guesser for HumanGuesser<R> {
+    fn next_guess(&mut self) -> Option<u32> {
+        let (min, max) = (self.range.start(), self.range.end());
+
+        ask_parsed(
+            &mut self.input,
+            &mut self.ask,
+            &format!("Please input a number between {min} and {max}:"),
+            &format!(
+                "Invalid value is given, expected a number between {min} and {max}. Guess again!"
+            ),
+        )
+    }
+}
+
+// The read-parse loop of the game: asks with `prompt` until a line parses into `T`,
+// every line that does not parse prints `invalid` and is asked again. `None` means input is over.
+// Both the number game and the word game read their guesses with it.
+pub fn read_parsed<T: FromStr>(input: &mut impl BufRead, prompt: &str, invalid: &str) -> Option<T> {
+    ask_parsed(input, &mut |text: &str| println!("{text}"), prompt, invalid)
+}
+
+// Same loop, but everything the player is told goes to `ask` instead of straight to stdout.
+// After a line that does not parse `ask` gets `invalid` and `prompt` together, so a view can show both at once.
+pub fn ask_parsed<T: FromStr>(
+    input: &mut impl BufRead,
+    ask: &mut impl FnMut(&str),
+    prompt: &str,
+    invalid: &str,
+) -> Option<T> {
+    let mut problem: Option<String> = None;
+
+    loop {
+        // `String::new()` will return new instance of String.
+        // :: syntax indicates that new is an associated function. It is a function that's implemented on a type.
+        let mut guess = String::new();
+
+        match problem.take() {
+            Some(problem) => ask(&format!("{problem}\n{prompt}")),
+            None => ask(prompt),
+        }
+
+        // We are using standart in function and calling readline function of it.
+        // `read_line` functin takes a parameter and it is about on which string it should store user entered text.
+        // note: `read_line` function appends entered text to given string and not overwrite its contents.
+        // `read_line` function takes a string reference and that means it takes a reference of variable's placement on memory
+        // and saying `&mut` means that we pass mutable reference of guess variable.
+        // Potential Failure Handling: on second line function `read_line` returns Result value. This Result value is an enumeration (enum)
+        // by calling `expect` function we are saying if result of `read_line` is Err it should print this message and crash the program
+        // If we comment `expect` function program will compile but still it will give us a warning about possible danger of not handling error.
+        // io::stdin()
+        //     .read_line(&mut guess)
+        //     .expect("Failed to read line");
+
+        // Here we handle the Result ourselves instead. `Ok(0)` means stdin was closed, so there is nothing left to guess.
+        match input.read_line(&mut guess) {
+            Ok(0) => return None,
+            Ok(_) => {}
+            Err(error) => {
+                ask(&format!("Failed to read line: {error}"));
+                return None;
+            }
+        }
+
+        // We already have a variable named guess but rust allows us to shadow the previous value with new one.
+        // This is called `Shadowing`
+        // here first we trim whitespaces on both and end of the original guess variable
+        // later we tell rust to parse it's type and the type is whatever `T` the caller asked for
+        // we add a error handling on situations where parsing might fail. (example: entering a char)
+        // let guess: u32 = guess.trim().parse().expect("Please type a number");
+
+        // this is another way of handling Result values with pattern matching
+        match guess.trim().parse() {
+            Ok(value) => return Some(value), // this will run if Result is handled without an error
+            Err(_) => {
+                problem = Some(invalid.to_string());
+                continue;
+            } // this will run if Result has an error, here it will skip current loop and start from start
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn invalid_lines_are_skipped() {
+        let mut human = HumanGuesser::new("abc\n\n 42 \n-1\n7".as_bytes(), 1..=100);
+
+        assert_eq!(human.next_guess(), Some(42));
+        assert_eq!(human.next_guess(), Some(7));
+        assert_eq!(human.next_guess(), None);
+    }
+
+    #[test]
+    fn prompts_go_to_the_given_sink() {
+        let asked = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&asked);
+        let mut human = HumanGuesser::new("abc\n5".as_bytes(), 1..=10)
+            .with_prompts(move |text| sink.borrow_mut().push(text.to_string()));
+
+        assert_eq!(human.next_guess(), Some(5));
+        assert_eq!(
+            *asked.borrow(),
+            [
+                "Please input a number between 1 and 10:",
+                "Invalid value is given, expected a number between 1 and 10. Guess again!\nPlease input a number between 1 and 10:",
+            ]
+        );
+    }
+}
@@ -0,0 +1,222 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// The game turned around: the player thinks of a number and the computer guesses it.
+// The player answers whether their number is higher or lower than the guess, or that it is correct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Answer {
+    Higher,
+    Lower,
+    Correct,
+}
+
+impl FromStr for Answer {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Answer, String> {
+        match text.trim().to_lowercase().as_str() {
+            "h" | "higher" | "+" | ">" => Ok(Answer::Higher),
+            "l" | "lower" | "-" | "<" => Ok(Answer::Lower),
+            "c" | "correct" | "=" | "y" | "yes" => Ok(Answer::Correct),
+            _ => Err(format!(
+                "unknown answer `{}`, expected higher, lower or correct",
+                text.trim()
+            )),
+        }
+    }
+}
+
+// Answers that no number can satisfy. Keeps the answers that set the bounds, so it can tell the player
+// which of their answers do not fit together, e.g. "lower than 40" and "higher than 60".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contradiction {
+    // the biggest guess the player said their number is higher than
+    pub higher_than: Option<u32>,
+    // the smallest guess the player said their number is lower than
+    pub lower_than: Option<u32>,
+    pub range: RangeInclusive<u32>,
+}
+
+impl fmt::Display for Contradiction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (min, max) = (self.range.start(), self.range.end());
+
+        match (self.higher_than, self.lower_than) {
+            (Some(higher), Some(lower)) => write!(
+                f,
+                "You said your number is higher than {higher} and lower than {lower}, no whole number is both."
+            ),
+            (Some(higher), None) => write!(
+                f,
+                "You said your number is higher than {higher}, but it has to be between {min} and {max}."
+            ),
+            (None, Some(lower)) => write!(
+                f,
+                "You said your number is lower than {lower}, but it has to be between {min} and {max}."
+            ),
+            (None, None) => write!(f, "No number between {min} and {max} fits your answers."),
+        }
+    }
+}
+
+impl std::error::Error for Contradiction {}
+
+#[derive(Debug, Clone)]
+pub struct ReverseGame {
+    range: RangeInclusive<u32>,
+    // the player's number is somewhere in `low..=high`
+    low: u32,
+    high: u32,
+    higher_than: Option<u32>,
+    lower_than: Option<u32>,
+    guesses: u32,
+    found: Option<u32>,
+}
+
+impl ReverseGame {
+    pub fn new(range: RangeInclusive<u32>) -> ReverseGame {
+        ReverseGame {
+            low: *range.start(),
+            high: *range.end(),
+            range,
+            higher_than: None,
+            lower_than: None,
+            guesses: 0,
+            found: None,
+        }
+    }
+
+    // Always the middle of what is still possible, so the number is found in at most log2(range) + 1 guesses.
+    pub fn guess(&self) -> u32 {
+        self.low + (self.high - self.low) / 2
+    }
+
+    // Applies the answer to the current guess. An answer that contradicts the earlier ones is refused
+    // and changes nothing, so the player can answer again instead of the computer guessing forever.
+    pub fn answer(&mut self, answer: Answer) -> Result<(), Contradiction> {
+        let guess = self.guess();
+
+        match answer {
+            Answer::Correct => self.found = Some(guess),
+            Answer::Higher => {
+                if guess >= self.high {
+                    return Err(self.contradiction(Some(guess), self.lower_than));
+                }
+                self.low = guess + 1;
+                self.higher_than = Some(guess);
+            }
+            Answer::Lower => {
+                if guess <= self.low {
+                    return Err(self.contradiction(self.higher_than, Some(guess)));
+                }
+                self.high = guess - 1;
+                self.lower_than = Some(guess);
+            }
+        }
+
+        self.guesses += 1;
+        Ok(())
+    }
+
+    fn contradiction(&self, higher_than: Option<u32>, lower_than: Option<u32>) -> Contradiction {
+        Contradiction {
+            higher_than,
+            lower_than,
+            range: self.range.clone(),
+        }
+    }
+
+    pub fn found(&self) -> Option<u32> {
+        self.found
+    }
+
+    pub fn guesses(&self) -> u32 {
+        self.guesses
+    }
+
+    pub fn range(&self) -> &RangeInclusive<u32> {
+        &self.range
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Answers like an honest player who thought of `secret`.
+    fn honest(game: &ReverseGame, secret: u32) -> Answer {
+        match secret.cmp(&game.guess()) {
+            std::cmp::Ordering::Greater => Answer::Higher,
+            std::cmp::Ordering::Less => Answer::Lower,
+            std::cmp::Ordering::Equal => Answer::Correct,
+        }
+    }
+
+    #[test]
+    fn answers_are_parsed() {
+        assert_eq!("H".parse(), Ok(Answer::Higher));
+        assert_eq!(" lower ".parse(), Ok(Answer::Lower));
+        assert_eq!("=".parse(), Ok(Answer::Correct));
+        assert!("maybe".parse::<Answer>().is_err());
+    }
+
+    #[test]
+    fn finds_every_number_of_the_range() {
+        for secret in 1..=100 {
+            let mut game = ReverseGame::new(1..=100);
+
+            while game.found().is_none() {
+                game.answer(honest(&game, secret)).unwrap();
+            }
+
+            assert_eq!(game.found(), Some(secret));
+            assert!(game.guesses() <= 7);
+        }
+    }
+
+    #[test]
+    fn contradicting_answers_are_explained() {
+        let mut game = ReverseGame::new(1..=100);
+
+        // 50 -> lower, 25 -> higher, 37 -> higher, 43 -> higher, 46 -> lower, 44 -> higher, 45 -> ?
+        for answer in [
+            Answer::Lower,
+            Answer::Higher,
+            Answer::Higher,
+            Answer::Higher,
+            Answer::Lower,
+            Answer::Higher,
+        ] {
+            game.answer(answer).unwrap();
+        }
+
+        assert_eq!(game.guess(), 45);
+        let error = game.answer(Answer::Lower).unwrap_err();
+        assert_eq!(error.higher_than, Some(44));
+        assert_eq!(error.lower_than, Some(45));
+        assert_eq!(
+            error.to_string(),
+            "You said your number is higher than 44 and lower than 45, no whole number is both."
+        );
+
+        // the refused answer changed nothing
+        assert_eq!(game.guess(), 45);
+        assert_eq!(game.guesses(), 6);
+        assert!(game.answer(Answer::Correct).is_ok());
+        assert_eq!(game.found(), Some(45));
+    }
+
+    #[test]
+    fn answers_outside_the_range_are_explained() {
+        let mut game = ReverseGame::new(1..=3);
+
+        game.answer(Answer::Higher).unwrap();
+        let error = game.answer(Answer::Higher).unwrap_err();
+
+        assert_eq!(
+            error.to_string(),
+            "You said your number is higher than 3, but it has to be between 1 and 3."
+        );
+    }
+}
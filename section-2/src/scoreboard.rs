@@ -0,0 +1,279 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use crate::difficulty::Difficulty;
+use crate::game::{GameSession, Status};
+
+// Scores are kept in a plain text file, one finished game per line and fields separated by tabs:
+// player, won, attempts, duration in milliseconds, min, max, finish time as unix seconds, difficulty
+// Files written before difficulties existed have no difficulty field, those games are read as custom.
+pub const DEFAULT_SCOREBOARD_FILE: &str = "guessing_game_scores.txt";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Score {
+    pub player: String,
+    pub won: bool,
+    pub attempts: u32,
+    pub duration: Duration,
+    pub difficulty: Difficulty,
+    pub min: u32,
+    pub max: u32,
+    pub finished_at: u64,
+}
+
+impl Score {
+    // Creates a score for a finished session, dated right now.
+    pub fn from_session(
+        player: &str,
+        session: &GameSession,
+        difficulty: Difficulty,
+        duration: Duration,
+    ) -> Score {
+        let finished_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_secs())
+            .unwrap_or(0);
+
+        Score {
+            // tabs and newlines would break the file format, so they are replaced with spaces
+            player: player.replace(['\t', '\n', '\r'], " ").trim().to_string(),
+            won: session.status() == Status::Won,
+            attempts: session.attempts(),
+            duration,
+            difficulty,
+            min: *session.range().start(),
+            max: *session.range().end(),
+            finished_at,
+        }
+    }
+
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.player,
+            self.won,
+            self.attempts,
+            self.duration.as_millis(),
+            self.min,
+            self.max,
+            self.finished_at,
+            self.difficulty
+        )
+    }
+
+    // How games are grouped on the leaderboard. Custom games are only comparable when their range is the same.
+    pub fn group_label(&self) -> String {
+        match self.difficulty {
+            Difficulty::Custom => format!("custom {}-{}", self.min, self.max),
+            difficulty => difficulty.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseScoreError(String);
+
+impl fmt::Display for ParseScoreError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "invalid score line: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseScoreError {}
+
+impl FromStr for Score {
+    type Err = ParseScoreError;
+
+    fn from_str(line: &str) -> Result<Score, ParseScoreError> {
+        let fields: Vec<&str> = line.split('\t').collect();
+
+        if fields.len() != 7 && fields.len() != 8 {
+            return Err(ParseScoreError(format!(
+                "expected 7 or 8 fields, got {}",
+                fields.len()
+            )));
+        }
+
+        // small helper so every field reports which one failed to parse
+        fn field<T: FromStr>(value: &str, name: &str) -> Result<T, ParseScoreError> {
+            value
+                .parse()
+                .map_err(|_| ParseScoreError(format!("bad {name} `{value}`")))
+        }
+
+        Ok(Score {
+            player: fields[0].to_string(),
+            won: field(fields[1], "won flag")?,
+            attempts: field(fields[2], "attempts")?,
+            duration: Duration::from_millis(field(fields[3], "duration")?),
+            difficulty: match fields.get(7) {
+                Some(difficulty) => field(difficulty, "difficulty")?,
+                None => Difficulty::Custom,
+            },
+            min: field(fields[4], "min")?,
+            max: field(fields[5], "max")?,
+            finished_at: field(fields[6], "date")?,
+        })
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Scoreboard {
+    pub scores: Vec<Score>,
+    // lines that could not be read, they are skipped instead of failing the whole file
+    pub skipped_lines: usize,
+}
+
+impl Scoreboard {
+    // A missing file is an empty scoreboard, not an error.
+    pub fn load(path: &Path) -> io::Result<Scoreboard> {
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Scoreboard::default()),
+            Err(error) => return Err(error),
+        };
+
+        Ok(Scoreboard::parse(&contents))
+    }
+
+    pub fn parse(contents: &str) -> Scoreboard {
+        let mut scoreboard = Scoreboard::default();
+
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match line.parse() {
+                Ok(score) => scoreboard.scores.push(score),
+                Err(_) => scoreboard.skipped_lines += 1,
+            }
+        }
+
+        scoreboard
+    }
+
+    pub fn append(path: &Path, score: &Score) -> io::Result<()> {
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        writeln!(file, "{}", score.to_line())
+    }
+
+    // Best `n` won games for each difficulty. Fewer attempts is better, on a tie the faster game wins.
+    pub fn top(&self, n: usize) -> BTreeMap<String, Vec<&Score>> {
+        let mut groups: BTreeMap<String, Vec<&Score>> = BTreeMap::new();
+
+        for score in self.scores.iter().filter(|score| score.won) {
+            groups.entry(score.group_label()).or_default().push(score);
+        }
+
+        for scores in groups.values_mut() {
+            scores.sort_by_key(|score| (score.attempts, score.duration));
+            scores.truncate(n);
+        }
+
+        groups
+    }
+}
+
+// Formats unix seconds as `YYYY-MM-DD` (UTC) without pulling in a date crate.
+// See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+pub fn format_date(unix_seconds: u64) -> String {
+    let days = (unix_seconds / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{year:04}-{month:02}-{day:02}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(player: &str, won: bool, attempts: u32, millis: u64, difficulty: Difficulty) -> Score {
+        Score {
+            player: player.to_string(),
+            won,
+            attempts,
+            duration: Duration::from_millis(millis),
+            difficulty,
+            min: *difficulty.range().start(),
+            max: *difficulty.range().end(),
+            finished_at: 0,
+        }
+    }
+
+    #[test]
+    fn score_line_round_trip() {
+        let original = score("alice", true, 5, 12_345, Difficulty::Hard);
+
+        assert_eq!(original.to_line().parse(), Ok(original));
+    }
+
+    #[test]
+    fn lines_without_difficulty_are_custom() {
+        let score: Score = "carol\ttrue\t4\t2500\t1\t100\t0".parse().unwrap();
+
+        assert_eq!(score.difficulty, Difficulty::Custom);
+        assert_eq!(score.group_label(), "custom 1-100");
+    }
+
+    #[test]
+    fn corrupt_lines_are_skipped() {
+        let good = score("bob", true, 3, 100, Difficulty::Normal).to_line();
+        let contents = format!("{good}\nnot a score\nbob\ttrue\tthree\t1\t1\t100\t0\n\n{good}\n");
+
+        let scoreboard = Scoreboard::parse(&contents);
+
+        assert_eq!(scoreboard.scores.len(), 2);
+        assert_eq!(scoreboard.skipped_lines, 2);
+    }
+
+    #[test]
+    fn missing_file_is_empty_scoreboard() {
+        let scoreboard = Scoreboard::load(Path::new("this/file/does/not/exist.txt")).unwrap();
+
+        assert!(scoreboard.scores.is_empty());
+    }
+
+    #[test]
+    fn top_groups_by_range_and_ranks_wins() {
+        let scoreboard = Scoreboard {
+            scores: vec![
+                score("slow", true, 4, 9_000, Difficulty::Normal),
+                score("lost", false, 1, 10, Difficulty::Normal),
+                score("fast", true, 4, 1_000, Difficulty::Normal),
+                score("best", true, 2, 5_000, Difficulty::Normal),
+                score("easy", true, 3, 1_000, Difficulty::Easy),
+                score("mine", true, 1, 1_000, Difficulty::Custom),
+            ],
+            skipped_lines: 0,
+        };
+
+        let top = scoreboard.top(2);
+        let names: Vec<&str> = top["normal"].iter().map(|s| s.player.as_str()).collect();
+
+        assert_eq!(names, vec!["best", "fast"]);
+        assert_eq!(top["easy"].len(), 1);
+        assert_eq!(top["custom 1-100"].len(), 1);
+    }
+
+    #[test]
+    fn dates_are_formatted() {
+        assert_eq!(format_date(0), "1970-01-01");
+        assert_eq!(format_date(951_782_400), "2000-02-29");
+        assert_eq!(format_date(1_700_000_000), "2023-11-14");
+    }
+}
@@ -0,0 +1,175 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::bot::BotKind;
+use crate::difficulty::Settings;
+use crate::game::{GameSession, Status, Summary};
+use crate::player::Guesser;
+
+// Plays a whole game without printing anything and returns how it ended.
+// Stops early if the guesser gives up or guesses something the engine refuses.
+pub fn play_headless(session: &mut GameSession, guesser: &mut dyn Guesser) -> Summary {
+    while !session.is_over() {
+        let Some(guess) = guesser.next_guess() else {
+            break;
+        };
+
+        match session.guess(guess) {
+            Ok(result) => guesser.feedback(guess, result.ordering),
+            Err(_) => break,
+        }
+    }
+
+    session.summary()
+}
+
+// Results of many headless games played by the same kind of bot.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Report {
+    pub games: usize,
+    pub wins: usize,
+    // attempts of every won game, sorted from best to worst
+    pub attempts: Vec<u32>,
+}
+
+impl Report {
+    pub fn losses(&self) -> usize {
+        self.games - self.wins
+    }
+
+    pub fn mean(&self) -> Option<f64> {
+        if self.attempts.is_empty() {
+            return None;
+        }
+
+        let total: u64 = self.attempts.iter().map(|&attempts| attempts as u64).sum();
+        Some(total as f64 / self.attempts.len() as f64)
+    }
+
+    pub fn median(&self) -> Option<f64> {
+        let count = self.attempts.len();
+
+        match count {
+            0 => None,
+            _ if count % 2 == 1 => Some(self.attempts[count / 2] as f64),
+            _ => Some((self.attempts[count / 2 - 1] + self.attempts[count / 2]) as f64 / 2.0),
+        }
+    }
+
+    pub fn worst(&self) -> Option<u32> {
+        self.attempts.last().copied()
+    }
+
+    // How many won games needed each number of attempts.
+    pub fn distribution(&self) -> BTreeMap<u32, usize> {
+        let mut distribution = BTreeMap::new();
+
+        for &attempts in &self.attempts {
+            *distribution.entry(attempts).or_insert(0) += 1;
+        }
+
+        distribution
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "games: {}, wins: {}, losses: {}",
+            self.games,
+            self.wins,
+            self.losses()
+        )?;
+
+        let (Some(mean), Some(median), Some(worst)) = (self.mean(), self.median(), self.worst())
+        else {
+            return writeln!(f, "no game was won");
+        };
+
+        writeln!(
+            f,
+            "attempts: mean {mean:.2}, median {median}, worst {worst}"
+        )?;
+
+        // bars are scaled so the most common attempt count gets 50 characters
+        let distribution = self.distribution();
+        let most = distribution.values().copied().max().unwrap_or(1);
+
+        for (attempts, count) in distribution {
+            let bar = "#".repeat((count * 50).div_ceil(most));
+            writeln!(f, "{attempts:>4} | {bar} {count}")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Plays `games` games with fresh secrets and a fresh bot each time.
+// Secrets and bot decisions come from `seed`, so the same arguments always give the same report.
+pub fn simulate(games: usize, bot: BotKind, settings: &Settings, seed: u64) -> Report {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut report = Report {
+        games,
+        ..Report::default()
+    };
+
+    for _ in 0..games {
+        let mut session =
+            GameSession::from_rng(&mut rng, settings.range.clone(), settings.max_attempts);
+        let mut guesser = bot.build(settings.range.clone(), rng.gen());
+
+        let summary = play_headless(&mut session, guesser.as_mut());
+
+        if summary.status == Status::Won {
+            report.wins += 1;
+            report.attempts.push(summary.attempts);
+        }
+    }
+
+    report.attempts.sort_unstable();
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::difficulty::Difficulty;
+
+    #[test]
+    fn statistics() {
+        let report = Report {
+            games: 5,
+            wins: 4,
+            attempts: vec![2, 3, 5, 8],
+        };
+
+        assert_eq!(report.losses(), 1);
+        assert_eq!(report.mean(), Some(4.5));
+        assert_eq!(report.median(), Some(4.0));
+        assert_eq!(report.worst(), Some(8));
+        assert_eq!(Report::default().median(), None);
+    }
+
+    #[test]
+    fn binary_bot_always_wins_hard_range() {
+        // the hard preset only allows 8 attempts, so give the bot the 14 a binary search needs
+        let settings = Settings::build(Some(Difficulty::Hard), None, None, Some(14)).unwrap();
+        let report = simulate(500, BotKind::Binary, &settings, 1);
+
+        assert_eq!(report.wins, 500);
+        assert!(report.worst().unwrap() <= 14);
+    }
+
+    #[test]
+    fn same_seed_same_report() {
+        let settings = Settings::default();
+
+        assert_eq!(
+            simulate(200, BotKind::Noisy, &settings, 3),
+            simulate(200, BotKind::Noisy, &settings, 3)
+        );
+    }
+}
@@ -0,0 +1,406 @@
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+
+use crate::difficulty::{Difficulty, Settings};
+use crate::game::{GameSession, GuessResult, Status, Summary};
+
+// A transcript is a JSON-lines file, every line is one of these events:
+// {"event":"start","seed":7,"difficulty":"normal","min":1,"max":100,"max_attempts":10}
+// {"event":"guess","guess":50,"ordering":"less","status":"playing"}
+// {"event":"end","status":"won","attempts":4,"secret":62}
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "lowercase")]
+pub enum Event {
+    Start {
+        seed: u64,
+        difficulty: Difficulty,
+        min: u32,
+        max: u32,
+        max_attempts: Option<u32>,
+    },
+    Guess {
+        guess: u32,
+        #[serde(with = "ordering_name")]
+        ordering: Ordering,
+        status: Status,
+    },
+    End {
+        status: Status,
+        attempts: u32,
+        secret: u32,
+    },
+}
+
+impl Event {
+    pub fn start(seed: u64, settings: &Settings) -> Event {
+        Event::Start {
+            seed,
+            difficulty: settings.difficulty,
+            min: settings.min(),
+            max: settings.max(),
+            max_attempts: settings.max_attempts,
+        }
+    }
+
+    pub fn guess(guess: u32, result: &GuessResult) -> Event {
+        Event::Guess {
+            guess,
+            ordering: result.ordering,
+            status: result.status,
+        }
+    }
+
+    pub fn end(summary: &Summary) -> Event {
+        Event::End {
+            status: summary.status,
+            attempts: summary.attempts,
+            secret: summary.secret,
+        }
+    }
+}
+
+// `Ordering` comes from the standard library and does not implement serde traits,
+// so it is written as "less", "greater" or "equal" by hand.
+mod ordering_name {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::cmp::Ordering;
+
+    pub fn serialize<S: Serializer>(ordering: &Ordering, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(match ordering {
+            Ordering::Less => "less",
+            Ordering::Greater => "greater",
+            Ordering::Equal => "equal",
+        })
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Ordering, D::Error> {
+        match String::deserialize(deserializer)?.as_str() {
+            "less" => Ok(Ordering::Less),
+            "greater" => Ok(Ordering::Greater),
+            "equal" => Ok(Ordering::Equal),
+            other => Err(D::Error::custom(format!("unknown ordering `{other}`"))),
+        }
+    }
+}
+
+// Writes events as they happen. Every line is flushed right away so a crashed game still leaves a usable transcript.
+pub struct Recorder<W: Write> {
+    out: W,
+}
+
+impl Recorder<BufWriter<File>> {
+    pub fn create(path: &Path) -> io::Result<Recorder<BufWriter<File>>> {
+        Ok(Recorder::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    pub fn new(out: W) -> Recorder<W> {
+        Recorder { out }
+    }
+
+    pub fn record(&mut self, event: &Event) -> io::Result<()> {
+        serde_json::to_writer(&mut self.out, event)?;
+        writeln!(self.out)?;
+        self.out.flush()
+    }
+}
+
+#[derive(Debug)]
+pub enum TranscriptError {
+    Io(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl fmt::Display for TranscriptError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TranscriptError::Io(error) => write!(f, "could not read transcript: {error}"),
+            TranscriptError::Parse { line, message } => write!(f, "line {line}: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for TranscriptError {}
+
+impl From<io::Error> for TranscriptError {
+    fn from(error: io::Error) -> TranscriptError {
+        TranscriptError::Io(error)
+    }
+}
+
+// Reads every event of a transcript, empty lines are ignored.
+pub fn read(reader: impl BufRead) -> Result<Vec<(usize, Event)>, TranscriptError> {
+    let mut events = Vec::new();
+
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let event = serde_json::from_str(&line).map_err(|error| TranscriptError::Parse {
+            line: index + 1,
+            message: error.to_string(),
+        })?;
+
+        events.push((index + 1, event));
+    }
+
+    Ok(events)
+}
+
+pub fn load(path: &Path) -> Result<Vec<(usize, Event)>, TranscriptError> {
+    read(io::BufReader::new(File::open(path)?))
+}
+
+// Everything that can go wrong when a transcript is played again.
+// `Mismatch` is the interesting one, it means the engine now answers differently than when the game was recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplayError {
+    MissingStart,
+    UnexpectedStart {
+        line: usize,
+    },
+    InvalidSettings {
+        line: usize,
+        message: String,
+    },
+    RejectedGuess {
+        line: usize,
+        message: String,
+    },
+    Mismatch {
+        line: usize,
+        expected: String,
+        actual: String,
+    },
+    EventAfterEnd {
+        line: usize,
+    },
+    MissingEnd,
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::MissingStart => write!(f, "transcript does not begin with a start event"),
+            ReplayError::UnexpectedStart { line } => {
+                write!(f, "line {line}: a game can only start once")
+            }
+            ReplayError::InvalidSettings { line, message } => write!(f, "line {line}: {message}"),
+            ReplayError::RejectedGuess { line, message } => {
+                write!(f, "line {line}: engine refused the guess: {message}")
+            }
+            ReplayError::Mismatch {
+                line,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "line {line}: recorded {expected} but replay gave {actual}"
+            ),
+            ReplayError::EventAfterEnd { line } => {
+                write!(f, "line {line}: event recorded after the game ended")
+            }
+            ReplayError::MissingEnd => write!(f, "transcript ends before the game's outcome"),
+        }
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+// Feeds the recorded guesses through a new session built from the recorded seed and settings,
+// and checks that every answer and the final outcome are the same as in the transcript.
+pub fn replay(events: &[(usize, Event)]) -> Result<Summary, ReplayError> {
+    let mut events = events.iter();
+
+    let mut session = match events.next() {
+        Some((
+            line,
+            Event::Start {
+                seed,
+                min,
+                max,
+                max_attempts,
+                ..
+            },
+        )) => {
+            if min >= max {
+                return Err(ReplayError::InvalidSettings {
+                    line: *line,
+                    message: format!("range {min}..={max} is invalid"),
+                });
+            }
+
+            GameSession::with_seed(*seed, *min..=*max, *max_attempts)
+        }
+        _ => return Err(ReplayError::MissingStart),
+    };
+
+    let mut ended = false;
+
+    for (line, event) in events {
+        let line = *line;
+
+        if ended {
+            return Err(ReplayError::EventAfterEnd { line });
+        }
+
+        match event {
+            Event::Start { .. } => return Err(ReplayError::UnexpectedStart { line }),
+            Event::Guess {
+                guess,
+                ordering,
+                status,
+            } => {
+                let result = session
+                    .guess(*guess)
+                    .map_err(|error| ReplayError::RejectedGuess {
+                        line,
+                        message: error.to_string(),
+                    })?;
+
+                if result.ordering != *ordering || result.status != *status {
+                    return Err(ReplayError::Mismatch {
+                        line,
+                        expected: format!("{ordering:?}/{status:?}"),
+                        actual: format!("{:?}/{:?}", result.ordering, result.status),
+                    });
+                }
+            }
+            Event::End { .. } => {
+                let actual = Event::end(&session.summary());
+
+                if actual != *event {
+                    return Err(ReplayError::Mismatch {
+                        line,
+                        expected: format!("{event:?}"),
+                        actual: format!("{actual:?}"),
+                    });
+                }
+
+                ended = true;
+            }
+        }
+    }
+
+    // a file cut short never recorded how the game ended, so there is nothing to verify it against
+    if !ended {
+        return Err(ReplayError::MissingEnd);
+    }
+
+    Ok(session.summary())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Plays a seeded game by binary search and records it the same way `main.rs` does.
+    fn record_game(seed: u64) -> Vec<u8> {
+        let settings = Settings::default();
+        let mut session =
+            GameSession::with_seed(seed, settings.range.clone(), settings.max_attempts);
+        let mut recorder = Recorder::new(Vec::new());
+
+        recorder.record(&Event::start(seed, &settings)).unwrap();
+
+        let (mut low, mut high) = (settings.min(), settings.max());
+        while !session.is_over() {
+            let guess = low + (high - low) / 2;
+            let result = session.guess(guess).unwrap();
+            recorder.record(&Event::guess(guess, &result)).unwrap();
+
+            match result.ordering {
+                Ordering::Less => low = guess + 1,
+                Ordering::Greater => high = guess - 1,
+                Ordering::Equal => {}
+            }
+        }
+
+        recorder.record(&Event::end(&session.summary())).unwrap();
+        recorder.out
+    }
+
+    #[test]
+    fn events_are_json_lines() {
+        let line = serde_json::to_string(&Event::Guess {
+            guess: 50,
+            ordering: Ordering::Less,
+            status: Status::Playing,
+        })
+        .unwrap();
+
+        assert_eq!(
+            line,
+            r#"{"event":"guess","guess":50,"ordering":"less","status":"playing"}"#
+        );
+    }
+
+    #[test]
+    fn recorded_game_replays_to_same_outcome() {
+        let transcript = record_game(99);
+        let events = read(transcript.as_slice()).unwrap();
+
+        let summary = replay(&events).unwrap();
+
+        assert_eq!(summary.status, Status::Won);
+        assert_eq!(Event::end(&summary), events.last().unwrap().1);
+    }
+
+    #[test]
+    fn tampered_transcript_is_a_mismatch() {
+        let transcript = record_game(99);
+        let mut events = read(transcript.as_slice()).unwrap();
+
+        // flip the answer of the first guess as if the engine had changed since recording
+        if let (_, Event::Guess { ordering, .. }) = &mut events[1] {
+            *ordering = ordering.reverse();
+        }
+
+        assert!(matches!(
+            replay(&events),
+            Err(ReplayError::Mismatch { line: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn bad_lines_report_line_number() {
+        let transcript = "{\"event\":\"start\",\"seed\":1,\"difficulty\":\"easy\",\"min\":1,\"max\":10,\"max_attempts\":5}\n\nnot json\n";
+
+        match read(transcript.as_bytes()) {
+            Err(TranscriptError::Parse { line, .. }) => assert_eq!(line, 3),
+            other => panic!("expected parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn transcript_must_start_with_start() {
+        let events = vec![(
+            1,
+            Event::End {
+                status: Status::Won,
+                attempts: 1,
+                secret: 1,
+            },
+        )];
+
+        assert_eq!(replay(&events), Err(ReplayError::MissingStart));
+    }
+
+    #[test]
+    fn truncated_transcript_is_an_error() {
+        let transcript = record_game(99);
+        let mut events = read(transcript.as_slice()).unwrap();
+
+        events.pop();
+
+        assert_eq!(replay(&events), Err(ReplayError::MissingEnd));
+    }
+}
@@ -0,0 +1,289 @@
+use std::cmp::Ordering;
+use std::io::{self, Write};
+use std::ops::RangeInclusive;
+
+use crate::difficulty::Settings;
+use crate::game::{GameSession, GuessError, Summary};
+use crate::view::View;
+
+// Terminal control sequences, see https://en.wikipedia.org/wiki/ANSI_escape_code
+// The alternate screen keeps the game from scrolling the player's terminal history away.
+const ENTER_ALTERNATE_SCREEN: &str = "\x1b[?1049h";
+const LEAVE_ALTERNATE_SCREEN: &str = "\x1b[?1049l";
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+// Width of the range bar in characters.
+const BAR_WIDTH: usize = 50;
+
+// Only the latest guesses fit on the screen.
+const HISTORY_ROWS: usize = 12;
+
+// Draws `low..=high` as the filled part of a bar that stands for the whole `range`.
+pub fn range_bar(range: &RangeInclusive<u32>, low: u32, high: u32, width: usize) -> String {
+    let start = *range.start() as u64;
+    let size = *range.end() as u64 - start + 1;
+    let width = width.max(1) as u64;
+
+    // which cell a value falls into, computed in u64 so big ranges do not overflow
+    let cell = |value: u32| ((value as u64 - start) * width / size) as usize;
+
+    let bar: String = if low > high {
+        "·".repeat(width as usize)
+    } else {
+        let (first, last) = (cell(low), cell(high));
+        (0..width as usize)
+            .map(|index| {
+                if (first..=last).contains(&index) {
+                    '█'
+                } else {
+                    '·'
+                }
+            })
+            .collect()
+    };
+
+    format!("[{bar}]")
+}
+
+// Everything the full-screen view shows. Kept apart from the terminal so it can be rendered in tests.
+#[derive(Debug, Clone)]
+pub struct Board {
+    range: RangeInclusive<u32>,
+    difficulty: String,
+    low: u32,
+    high: u32,
+    // narrowing the bar would give away the direction in hint modes that hide it
+    reveal_direction: bool,
+    history: Vec<(u32, String)>,
+    attempts: u32,
+    max_attempts: Option<u32>,
+    message: String,
+    prompt: String,
+}
+
+impl Board {
+    pub fn new(settings: &Settings, reveal_direction: bool) -> Board {
+        Board {
+            range: settings.range.clone(),
+            difficulty: settings.difficulty.to_string(),
+            low: settings.min(),
+            high: settings.max(),
+            reveal_direction,
+            history: Vec::new(),
+            attempts: 0,
+            max_attempts: settings.max_attempts,
+            message: String::from("Type a number and press enter."),
+            prompt: String::new(),
+        }
+    }
+
+    pub fn guessed(&mut self, guess: u32, ordering: Ordering, hint: &str, attempts: u32) {
+        if self.reveal_direction {
+            match ordering {
+                Ordering::Less => self.low = self.low.max(guess.saturating_add(1)),
+                Ordering::Greater => self.high = self.high.min(guess.saturating_sub(1)),
+                Ordering::Equal => (self.low, self.high) = (guess, guess),
+            }
+        }
+
+        // multi line hints are shown on one row in the table
+        self.history.push((guess, hint.replace('\n', " ")));
+        self.attempts = attempts;
+        self.message = hint.to_string();
+    }
+
+    pub fn set_message(&mut self, message: &str) {
+        self.message = message.to_string();
+    }
+
+    // Shown under the message, right above where the player types.
+    pub fn set_prompt(&mut self, prompt: &str) {
+        self.prompt = prompt.to_string();
+    }
+
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            format!(
+                " GUESS THE NUMBER  {}..={}  ({} difficulty)",
+                self.range.start(),
+                self.range.end(),
+                self.difficulty
+            ),
+            String::new(),
+        ];
+
+        if self.reveal_direction {
+            lines.push(format!(" Still possible: {} - {}", self.low, self.high));
+        } else {
+            lines.push(String::from(" Still possible: (hidden by hint mode)"));
+        }
+        lines.push(format!(
+            " {}",
+            range_bar(&self.range, self.low, self.high, BAR_WIDTH)
+        ));
+
+        lines.push(match self.max_attempts {
+            Some(max) => format!(" Attempts: {} / {max}", self.attempts),
+            None => format!(" Attempts: {}", self.attempts),
+        });
+        lines.push(String::new());
+        lines.push(format!(" {:>3}  {:>10}  Result", "#", "Guess"));
+
+        let skipped = self.history.len().saturating_sub(HISTORY_ROWS);
+        for (index, (guess, hint)) in self.history.iter().enumerate().skip(skipped) {
+            lines.push(format!(" {:>3}  {guess:>10}  {hint}", index + 1));
+        }
+
+        lines.push(String::new());
+        for line in self.message.lines() {
+            lines.push(format!(" {line}"));
+        }
+
+        if !self.prompt.is_empty() {
+            lines.push(String::new());
+            for line in self.prompt.lines() {
+                lines.push(format!(" {line}"));
+            }
+        }
+
+        lines.join("\n") + "\n"
+    }
+}
+
+// Full-screen view: redraws the whole board after every guess on the alternate screen.
+// When the game ends the screen is restored and the final board is printed once, so it stays visible.
+pub struct TuiView {
+    board: Option<Board>,
+    reveal_direction: bool,
+    active: bool,
+}
+
+impl TuiView {
+    pub fn new(reveal_direction: bool) -> TuiView {
+        TuiView {
+            board: None,
+            reveal_direction,
+            active: false,
+        }
+    }
+
+    fn draw(&self) {
+        if let Some(board) = &self.board {
+            print!("{CLEAR_SCREEN}{}", board.render());
+            // drawing is best effort, a closed terminal will show up as a failed read anyway
+            io::stdout().flush().ok();
+        }
+    }
+
+    fn leave(&mut self) {
+        if self.active {
+            print!("{LEAVE_ALTERNATE_SCREEN}");
+            io::stdout().flush().ok();
+            self.active = false;
+        }
+    }
+}
+
+impl View for TuiView {
+    fn start(&mut self, settings: &Settings) {
+        print!("{ENTER_ALTERNATE_SCREEN}");
+        self.active = true;
+        self.board = Some(Board::new(settings, self.reveal_direction));
+        self.draw();
+    }
+
+    fn guessed(&mut self, guess: u32, ordering: Ordering, hint: &str, session: &GameSession) {
+        if let Some(board) = &mut self.board {
+            board.guessed(guess, ordering, hint, session.attempts());
+        }
+        self.draw();
+    }
+
+    fn refused(&mut self, _guess: u32, error: &GuessError) {
+        if let Some(board) = &mut self.board {
+            board.set_message(&error.to_string());
+        }
+        self.draw();
+    }
+
+    fn finished(&mut self, summary: &Summary) {
+        self.leave();
+
+        if let Some(board) = &mut self.board {
+            board.set_message(&summary.to_string());
+            board.set_prompt("");
+            print!("{}", board.render());
+        }
+    }
+
+    fn prompt(&mut self, text: &str) {
+        if let Some(board) = &mut self.board {
+            board.set_prompt(text);
+        }
+        self.draw();
+    }
+}
+
+// Makes sure the terminal is usable again even if the game stops early, e.g. when stdin is closed.
+impl Drop for TuiView {
+    fn drop(&mut self) {
+        self.leave();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bar_shrinks_with_window() {
+        assert_eq!(range_bar(&(1..=10), 1, 10, 10), "[██████████]");
+        assert_eq!(range_bar(&(1..=10), 4, 6, 10), "[···███····]");
+        assert_eq!(range_bar(&(1..=10), 6, 5, 10), "[··········]");
+        assert_eq!(range_bar(&(0..=u32::MAX), 0, 0, 4), "[█···]");
+    }
+
+    #[test]
+    fn board_shows_history_and_attempts() {
+        let mut board = Board::new(&Settings::default(), true);
+
+        board.guessed(50, Ordering::Less, "Too small", 1);
+        board.guessed(75, Ordering::Greater, "Too big", 2);
+
+        let screen = board.render();
+
+        assert!(screen.contains("Still possible: 51 - 74"));
+        assert!(screen.contains("Attempts: 2 / 10"));
+        assert!(screen.contains("  1          50  Too small"));
+        assert!(screen.contains("  2          75  Too big"));
+    }
+
+    #[test]
+    fn prompt_is_drawn_below_the_message() {
+        let mut board = Board::new(&Settings::default(), true);
+
+        board.guessed(50, Ordering::Less, "Too small", 1);
+        board.set_prompt("Invalid value is given.\nPlease input a number between 1 and 100:");
+
+        let screen = board.render();
+        let message = screen.find(" Too small\n").unwrap();
+        let prompt = screen
+            .rfind(" Please input a number between 1 and 100:")
+            .unwrap();
+
+        assert!(message < prompt);
+        assert!(screen.contains(" Invalid value is given.\n"));
+    }
+
+    #[test]
+    fn hidden_direction_keeps_full_bar() {
+        let mut board = Board::new(&Settings::default(), false);
+
+        board.guessed(50, Ordering::Less, "Cold", 1);
+
+        assert!(board.render().contains("(hidden by hint mode)"));
+        assert!(board
+            .render()
+            .contains(&range_bar(&(1..=100), 1, 100, BAR_WIDTH)));
+    }
+}
@@ -0,0 +1,61 @@
+use std::cmp::Ordering;
+
+use crate::difficulty::Settings;
+use crate::game::{GameSession, GuessError, Summary};
+
+// How a game is shown to the player. The game loop in `main.rs` only tells the view what happened,
+// the plain line output and the full-screen terminal UI decide how to draw it.
+pub trait View {
+    fn start(&mut self, settings: &Settings);
+
+    // `hint` is what the active hint strategy says about the guess, "You win!" for a correct one.
+    fn guessed(&mut self, guess: u32, ordering: Ordering, hint: &str, session: &GameSession);
+
+    fn refused(&mut self, guess: u32, error: &GuessError);
+
+    fn finished(&mut self, summary: &Summary);
+
+    // What the player is asked before typing a guess, it can span several lines.
+    fn prompt(&mut self, text: &str);
+}
+
+// The original output of the game, one line per event. Works in pipes and in tests.
+pub struct LineView;
+
+impl View for LineView {
+    fn start(&mut self, settings: &Settings) {
+        println!(
+            "Guess the number between {} and {}! ({} difficulty)",
+            settings.min(),
+            settings.max(),
+            settings.difficulty
+        );
+
+        if let Some(max_attempts) = settings.max_attempts {
+            println!("You have {max_attempts} attempts.");
+        }
+    }
+
+    fn guessed(&mut self, guess: u32, _ordering: Ordering, hint: &str, session: &GameSession) {
+        // We can use variables inside strings using curly brackets. Anything between curly brackets will be printed.
+        println!("You guessed: {guess}");
+        println!("{hint}");
+
+        if let (false, Some(remaining)) = (session.is_over(), session.remaining_attempts()) {
+            println!("{remaining} attempts left.");
+        }
+    }
+
+    fn refused(&mut self, guess: u32, error: &GuessError) {
+        println!("You guessed: {guess}");
+        println!("{error}");
+    }
+
+    fn finished(&mut self, summary: &Summary) {
+        println!("{summary}");
+    }
+
+    fn prompt(&mut self, text: &str) {
+        println!("{text}");
+    }
+}
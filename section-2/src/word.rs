@@ -0,0 +1,357 @@
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::game::Status;
+
+pub const WORD_LENGTH: usize = 5;
+pub const MAX_WORD_ATTEMPTS: u32 = 6;
+
+// The word list that ships with the game, one lowercase word per line.
+const BUILT_IN_WORDS: &str = include_str!("../words.txt");
+
+// A guess that has the right shape: five ascii letters, stored lowercase.
+// Whether it is a real word is checked against the word list by `WordGame::guess`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Word(String);
+
+impl Word {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Word {
+    type Err = WordError;
+
+    fn from_str(text: &str) -> Result<Word, WordError> {
+        let word = text.trim().to_lowercase();
+
+        if word.chars().count() != WORD_LENGTH || !word.chars().all(|c| c.is_ascii_lowercase()) {
+            return Err(WordError::NotAWord(text.trim().to_string()));
+        }
+
+        Ok(Word(word))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordError {
+    NotAWord(String),
+    NotInList(String),
+    GameOver,
+}
+
+impl fmt::Display for WordError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            WordError::NotAWord(text) => {
+                write!(
+                    f,
+                    "`{text}` is not a {WORD_LENGTH} letter word. Guess again!"
+                )
+            }
+            WordError::NotInList(word) => {
+                write!(f, "`{word}` is not in the word list. Guess again!")
+            }
+            WordError::GameOver => write!(f, "The game is already over."),
+        }
+    }
+}
+
+impl std::error::Error for WordError {}
+
+#[derive(Debug, Clone)]
+pub struct WordList {
+    words: Vec<Word>,
+}
+
+impl WordList {
+    pub fn built_in() -> WordList {
+        WordList::parse(BUILT_IN_WORDS)
+    }
+
+    // Lines that are not five letter words are ignored, so any plain word list can be used.
+    pub fn parse(contents: &str) -> WordList {
+        let mut words: Vec<Word> = contents
+            .lines()
+            .filter_map(|line| line.parse().ok())
+            .collect();
+
+        // sorted and without duplicates, so the same list always gives the same secret for a seed
+        words.sort_by(|a, b| a.0.cmp(&b.0));
+        words.dedup();
+
+        WordList { words }
+    }
+
+    pub fn load(path: &Path) -> io::Result<WordList> {
+        let list = WordList::parse(&fs::read_to_string(path)?);
+
+        if list.words.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("{} has no {WORD_LENGTH} letter words", path.display()),
+            ));
+        }
+
+        Ok(list)
+    }
+
+    pub fn contains(&self, word: &Word) -> bool {
+        self.words
+            .binary_search_by(|probe| probe.0.cmp(&word.0))
+            .is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn pick(&self, seed: u64) -> Word {
+        let index = StdRng::seed_from_u64(seed).gen_range(0..self.words.len());
+        self.words[index].clone()
+    }
+}
+
+// Days since 1970-01-01 in UTC. Everyone playing on the same day gets the same daily word.
+pub fn today() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_secs() / 86_400)
+        .unwrap_or(0)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Letter {
+    // right letter in the right place
+    Correct,
+    // letter is in the word but somewhere else
+    Present,
+    Absent,
+}
+
+impl Letter {
+    pub fn emoji(&self) -> char {
+        match self {
+            Letter::Correct => '🟩',
+            Letter::Present => '🟨',
+            Letter::Absent => '⬛',
+        }
+    }
+}
+
+// Compares a guess with the secret letter by letter.
+// Letters that appear more than once are only marked as often as they appear in the secret,
+// exact matches are counted first, e.g. guessing "eerie" for "liner" marks only the first e as `Present`.
+pub fn score(guess: &Word, secret: &Word) -> [Letter; WORD_LENGTH] {
+    let guess = guess.0.as_bytes();
+    let secret = secret.0.as_bytes();
+    let mut result = [Letter::Absent; WORD_LENGTH];
+    // how many of each letter of the secret are not used by a match yet
+    let mut unused = [0u8; 26];
+
+    for index in 0..WORD_LENGTH {
+        if guess[index] == secret[index] {
+            result[index] = Letter::Correct;
+        } else {
+            unused[(secret[index] - b'a') as usize] += 1;
+        }
+    }
+
+    for index in 0..WORD_LENGTH {
+        let letter = (guess[index] - b'a') as usize;
+
+        if result[index] != Letter::Correct && unused[letter] > 0 {
+            unused[letter] -= 1;
+            result[index] = Letter::Present;
+        }
+    }
+
+    result
+}
+
+// One round of the word game.
+#[derive(Debug)]
+pub struct WordGame {
+    secret: Word,
+    history: Vec<(Word, [Letter; WORD_LENGTH])>,
+    status: Status,
+}
+
+impl WordGame {
+    pub fn new(secret: Word) -> WordGame {
+        WordGame {
+            secret,
+            history: Vec::new(),
+            status: Status::Playing,
+        }
+    }
+
+    pub fn guess(
+        &mut self,
+        guess: &Word,
+        list: &WordList,
+    ) -> Result<[Letter; WORD_LENGTH], WordError> {
+        if self.status != Status::Playing {
+            return Err(WordError::GameOver);
+        }
+
+        // the secret is always allowed, even when it comes from a different list
+        if guess != &self.secret && !list.contains(guess) {
+            return Err(WordError::NotInList(guess.to_string()));
+        }
+
+        let letters = score(guess, &self.secret);
+        self.history.push((guess.clone(), letters));
+
+        self.status = if guess == &self.secret {
+            Status::Won
+        } else if self.history.len() as u32 >= MAX_WORD_ATTEMPTS {
+            Status::Lost
+        } else {
+            Status::Playing
+        };
+
+        Ok(letters)
+    }
+
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    pub fn attempts(&self) -> u32 {
+        self.history.len() as u32
+    }
+
+    pub fn secret(&self) -> &Word {
+        &self.secret
+    }
+
+    // The result grid without letters, so it can be shared without spoiling the word:
+    //
+    // Word game 20379 3/6
+    // ⬛🟨⬛⬛⬛
+    // 🟩⬛🟨🟨⬛
+    // 🟩🟩🟩🟩🟩
+    pub fn share(&self, title: &str) -> String {
+        let attempts = match self.status {
+            Status::Won => self.attempts().to_string(),
+            _ => String::from("X"),
+        };
+
+        let mut grid = format!("{title} {attempts}/{MAX_WORD_ATTEMPTS}");
+
+        for (_, letters) in &self.history {
+            grid.push('\n');
+            grid.extend(letters.iter().map(Letter::emoji));
+        }
+
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use Letter::*;
+
+    fn word(text: &str) -> Word {
+        text.parse().unwrap()
+    }
+
+    #[test]
+    fn words_must_be_five_letters() {
+        assert_eq!(word(" CRANE "), Word(String::from("crane")));
+        assert!("cranes".parse::<Word>().is_err());
+        assert!("cr4ne".parse::<Word>().is_err());
+        assert!("".parse::<Word>().is_err());
+    }
+
+    #[test]
+    fn letters_are_scored() {
+        assert_eq!(score(&word("crane"), &word("crane")), [Correct; 5]);
+        assert_eq!(
+            score(&word("react"), &word("crane")),
+            [Present, Present, Correct, Present, Absent]
+        );
+    }
+
+    #[test]
+    fn repeated_letters_are_only_marked_as_often_as_in_secret() {
+        assert_eq!(
+            score(&word("geese"), &word("those")),
+            [Absent, Absent, Absent, Correct, Correct]
+        );
+        assert_eq!(
+            score(&word("eerie"), &word("liner")),
+            [Present, Absent, Present, Present, Absent]
+        );
+    }
+
+    #[test]
+    fn guesses_are_checked_against_list() {
+        let list = WordList::parse("crane\nslate\nnot-a-word\n");
+        let mut game = WordGame::new(word("crane"));
+
+        assert_eq!(list.len(), 2);
+        assert_eq!(
+            game.guess(&word("zzzzz"), &list),
+            Err(WordError::NotInList(String::from("zzzzz")))
+        );
+        assert_eq!(game.attempts(), 0);
+        assert!(game.guess(&word("slate"), &list).is_ok());
+        assert_eq!(game.guess(&word("crane"), &list), Ok([Correct; 5]));
+        assert_eq!(game.status(), Status::Won);
+    }
+
+    #[test]
+    fn six_misses_lose() {
+        let list = WordList::parse("crane\nslate");
+        let mut game = WordGame::new(word("crane"));
+
+        for _ in 0..MAX_WORD_ATTEMPTS {
+            game.guess(&word("slate"), &list).unwrap();
+        }
+
+        assert_eq!(game.status(), Status::Lost);
+        assert!(game.share("Word game").starts_with("Word game X/6\n"));
+    }
+
+    #[test]
+    fn share_grid_has_one_row_per_guess() {
+        let list = WordList::parse("react");
+        let mut game = WordGame::new(word("crane"));
+
+        game.guess(&word("react"), &list).unwrap();
+        game.guess(&word("crane"), &list).unwrap();
+
+        assert_eq!(
+            game.share("Word game 1"),
+            "Word game 1 2/6\n🟨🟨🟩🟨⬛\n🟩🟩🟩🟩🟩"
+        );
+    }
+
+    #[test]
+    fn same_seed_same_word() {
+        let list = WordList::built_in();
+
+        assert!(list.len() > 100);
+        assert_eq!(list.pick(20_000), list.pick(20_000));
+    }
+}
@@ -0,0 +1,264 @@
+use std::path::PathBuf;
+
+use crate::rules::Combine;
+use crate::scalars::{Expression, ScalarType};
+use crate::timer::Plan;
+
+// The range `--rules` goes over when `--start` and `--end` are not given.
+pub const DEFAULT_START: i64 = 1;
+pub const DEFAULT_END: i64 = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Mode {
+    // no arguments: run the examples of the chapter
+    Tutorial,
+    // `section-3 --rules rules.txt --start 1 --end 30 --mode first`, FizzBuzz without a file
+    Rules {
+        file: Option<PathBuf>,
+        start: i64,
+        end: i64,
+        // overrides the `mode` line of the file
+        combine: Option<Combine>,
+    },
+    // `section-3 --timer "warmup 1m, 3x(work 30s, rest 10s)"`
+    Timer {
+        plan: Plan,
+    },
+    // `section-3 --types`: size, min and max of every integer and float type
+    Types,
+    // `section-3 --eval "200 + 100" --type u8,i32`, every type when `--type` is not given
+    Evaluate {
+        text: String,
+        expression: Expression,
+        types: Vec<ScalarType>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    pub mode: Mode,
+}
+
+impl Config {
+    pub fn build(mut args: impl Iterator<Item = String>) -> Result<Config, String> {
+        args.next();
+
+        let mut args = args.peekable();
+        let mut rules = false;
+        let mut file = None;
+        let mut start = None;
+        let mut end = None;
+        let mut combine = None;
+        let mut timer = None;
+        let mut types = false;
+        let mut evaluate = None;
+        let mut scalar_types = None;
+        // `--rules`, `--timer`, `--types` and `--eval` each run a different program
+        let mut programs = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--rules" => {
+                    rules = true;
+                    // the file is optional, FizzBuzz is played without it
+                    if let Some(next) = args.next_if(|next| !next.starts_with("--")) {
+                        file = Some(PathBuf::from(next));
+                    }
+                }
+                "--start" => start = Some(number_of(&arg, args.next())?),
+                "--end" => end = Some(number_of(&arg, args.next())?),
+                "--mode" => combine = Some(value_of(&arg, args.next())?.parse()?),
+                "--timer" => {
+                    let text = value_of(&arg, args.next())?;
+                    let plan = Plan::parse(&text)
+                        .map_err(|err| format!("{err}\n{}", err.pointer(&text)))?;
+                    timer = Some(plan);
+                }
+                "--types" => types = true,
+                "--eval" => {
+                    let text = value_of(&arg, args.next())?;
+                    let expression = Expression::parse(&text)
+                        .map_err(|err| format!("{err}\n{}", err.pointer(&text)))?;
+                    evaluate = Some((text, expression));
+                }
+                "--type" => {
+                    let value = value_of(&arg, args.next())?;
+                    let parsed: Result<Vec<ScalarType>, String> =
+                        value.split(',').map(str::parse).collect();
+                    scalar_types = Some(parsed?);
+                }
+                other => return Err(format!("unknown argument `{other}`")),
+            }
+
+            if ["--rules", "--timer", "--types", "--eval"].contains(&arg.as_str()) {
+                programs.push(arg);
+            }
+        }
+
+        if let [first, second, ..] = programs.as_slice() {
+            return Err(format!("`{first}` and `{second}` cannot be used together"));
+        }
+
+        if scalar_types.is_some() && evaluate.is_none() {
+            return Err(String::from("`--type` only works together with `--eval`"));
+        }
+
+        if let Some(plan) = timer {
+            return Ok(Config {
+                mode: Mode::Timer { plan },
+            });
+        }
+
+        if types {
+            return Ok(Config { mode: Mode::Types });
+        }
+
+        if let Some((text, expression)) = evaluate {
+            return Ok(Config {
+                mode: Mode::Evaluate {
+                    text,
+                    expression,
+                    types: scalar_types.unwrap_or(ScalarType::ALL.to_vec()),
+                },
+            });
+        }
+
+        if !rules {
+            if start.is_some() || end.is_some() || combine.is_some() {
+                return Err(String::from(
+                    "`--start`, `--end` and `--mode` only work together with `--rules`",
+                ));
+            }
+
+            return Ok(Config {
+                mode: Mode::Tutorial,
+            });
+        }
+
+        let start = start.unwrap_or(DEFAULT_START);
+        let end = end.unwrap_or(DEFAULT_END);
+        if start > end {
+            return Err(format!("`--start {start}` is after `--end {end}`"));
+        }
+
+        Ok(Config {
+            mode: Mode::Rules {
+                file,
+                start,
+                end,
+                combine,
+            },
+        })
+    }
+}
+
+fn value_of(flag: &str, value: Option<String>) -> Result<String, String> {
+    value.ok_or_else(|| format!("`{flag}` needs a value"))
+}
+
+fn number_of(flag: &str, value: Option<String>) -> Result<i64, String> {
+    let value = value_of(flag, value)?;
+
+    value
+        .parse()
+        .map_err(|_| format!("`{value}` is not a valid value for `{flag}`"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(args: &[&str]) -> Result<Config, String> {
+        let args = std::iter::once("section-3")
+            .chain(args.iter().copied())
+            .map(String::from);
+
+        Config::build(args)
+    }
+
+    #[test]
+    fn no_arguments_is_the_tutorial() {
+        assert_eq!(build(&[]).unwrap().mode, Mode::Tutorial);
+    }
+
+    #[test]
+    fn rules_mode() {
+        assert_eq!(
+            build(&["--rules"]).unwrap().mode,
+            Mode::Rules {
+                file: None,
+                start: DEFAULT_START,
+                end: DEFAULT_END,
+                combine: None
+            }
+        );
+        assert_eq!(
+            build(&[
+                "--rules",
+                "rules.txt",
+                "--start",
+                "-5",
+                "--end",
+                "5",
+                "--mode",
+                "first"
+            ])
+            .unwrap()
+            .mode,
+            Mode::Rules {
+                file: Some(PathBuf::from("rules.txt")),
+                start: -5,
+                end: 5,
+                combine: Some(Combine::FirstMatch)
+            }
+        );
+    }
+
+    #[test]
+    fn timer_mode() {
+        let Mode::Timer { plan } = build(&["--timer", "2x(work 30s, rest 10s)"]).unwrap().mode
+        else {
+            panic!("expected a timer");
+        };
+
+        assert_eq!(plan.schedule().len(), 4);
+    }
+
+    #[test]
+    fn scalar_modes() {
+        assert_eq!(build(&["--types"]).unwrap().mode, Mode::Types);
+
+        let Mode::Evaluate { text, types, .. } =
+            build(&["--eval", "200 + 100", "--type", "u8,i32"])
+                .unwrap()
+                .mode
+        else {
+            panic!("expected an evaluation");
+        };
+        assert_eq!(text, "200 + 100");
+        assert_eq!(types, [ScalarType::U8, ScalarType::I32]);
+
+        let Mode::Evaluate { types, .. } = build(&["--eval", "1 + 1"]).unwrap().mode else {
+            panic!("expected an evaluation");
+        };
+        assert_eq!(types.len(), ScalarType::ALL.len());
+
+        let err = build(&["--eval", "200 + x"]).unwrap_err();
+        assert!(err.ends_with("200 + x\n      ^"), "error was: {err}");
+        assert!(build(&["--eval", "1", "--type", "u7"]).is_err());
+        assert!(build(&["--type", "u8"]).is_err());
+        assert!(build(&["--types", "--eval", "1"]).is_err());
+    }
+
+    #[test]
+    fn bad_arguments_are_errors() {
+        assert!(build(&["--start", "1"]).is_err());
+        assert!(build(&["--rules", "--start", "ten"]).is_err());
+        assert!(build(&["--rules", "--start", "5", "--end", "1"]).is_err());
+        assert!(build(&["--rules", "--mode", "last"]).is_err());
+        assert!(build(&["--fast"]).is_err());
+        assert!(build(&["--timer"]).is_err());
+        assert!(build(&["--timer", "work"]).is_err());
+        assert!(build(&["--timer", "work 1s", "--rules"]).is_err());
+    }
+}
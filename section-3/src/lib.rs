@@ -0,0 +1,11 @@
+// The programs built on top of the control flow chapter live in this library crate so they can be tested.
+// `main.rs` still walks through the chapter itself and only hands over when an option asks for a program.
+pub mod config;
+pub mod rules;
+pub mod scalars;
+pub mod timer;
+
+pub use config::{Config, Mode};
+pub use rules::{Combine, Rule, RuleError, RuleErrorKind, RuleSet};
+pub use scalars::{Expression, ExpressionError, ScalarType, Semantics};
+pub use timer::{Clock, Command, ManualClock, Plan, PlanError, Summary, SystemClock, Timer};
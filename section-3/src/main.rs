@@ -1,8 +1,130 @@
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::{env, fs, process, thread};
+
+use section_3::scalars::{self, Expression, ScalarType};
+use section_3::timer::{format_duration, Event};
+use section_3::{Combine, Command, Config, Mode, Plan, RuleSet, SystemClock, Timer};
+
 fn main() {
-    // variables_and_mutability();
-    // data_types();
-    // functions();
-    control_flow();
+    let config = Config::build(env::args()).unwrap_or_else(|err| {
+        eprintln!("Problem parsing arguments: {err}");
+        process::exit(1);
+    });
+
+    match config.mode {
+        Mode::Tutorial => {
+            // variables_and_mutability();
+            // data_types();
+            // functions();
+            control_flow();
+        }
+        Mode::Rules {
+            ref file,
+            start,
+            end,
+            combine,
+        } => divisibility_rules(file, start, end, combine),
+        Mode::Timer { ref plan } => countdown(plan),
+        Mode::Types => print!("{}", scalars::types_table()),
+        Mode::Evaluate {
+            ref text,
+            ref expression,
+            ref types,
+        } => evaluate(text, expression, types),
+    }
+}
+
+// What `scalar_types` only tells about: the same arithmetic in every type and every overflow method.
+fn evaluate(text: &str, expression: &Expression, types: &[ScalarType]) {
+    println!("{text}\n");
+    print!("{}", scalars::evaluation_table(expression, types));
+    println!("\nplain (debug) is `a + b` in a debug build, a release build wraps around like wrapping does.");
+}
+
+// The `while number != 0` countdown of `control_flow`, but one second at a time and with named phases.
+fn countdown(plan: &Plan) {
+    // stdin is read on its own thread so waiting for a line never holds up the countdown
+    let (sender, commands) = mpsc::channel();
+    thread::spawn(move || {
+        for line in io::stdin().lines() {
+            let Ok(line) = line else {
+                break;
+            };
+
+            match line.parse::<Command>() {
+                Ok(command) => {
+                    if sender.send(command).is_err() {
+                        break;
+                    }
+                }
+                Err(err) => eprintln!("{err}"),
+            }
+        }
+    });
+
+    println!("Press enter to pause or resume, type q and enter to stop.");
+
+    let timer = Timer::new(plan);
+    let mut current = String::new();
+
+    let summary = timer.run(
+        &SystemClock::default(),
+        || commands.try_recv().ok(),
+        |event| {
+            match event {
+                Event::Started(phase) => {
+                    let rounds: Vec<String> = phase
+                        .rounds
+                        .iter()
+                        .map(|round| format!("{}/{}", round.current, round.of))
+                        .collect();
+
+                    current = match rounds.is_empty() {
+                        true => phase.name.clone(),
+                        false => format!("{} ({})", phase.name, rounds.join(", ")),
+                    };
+                    print!("{current} {}", format_duration(phase.duration));
+                }
+                // the line is written over every second
+                Event::Tick { remaining } => print!("\r{current} {}   ", format_duration(remaining)),
+                Event::Paused => print!("\r{current} paused"),
+                Event::Resumed => print!("\r{current}       "),
+                Event::Ended(_) => println!(),
+            }
+
+            io::stdout().flush().unwrap_or(());
+        },
+    );
+
+    println!("\n{summary}");
+}
+
+// The `if num2 % 4` chain in `control_flow`, but with the divisors and words read from a file.
+fn divisibility_rules(file: &Option<PathBuf>, start: i64, end: i64, combine: Option<Combine>) {
+    let mut rules = match file {
+        Some(path) => {
+            let text = fs::read_to_string(path).unwrap_or_else(|err| {
+                eprintln!("Could not read {}: {err}", path.display());
+                process::exit(1);
+            });
+
+            RuleSet::parse(&text).unwrap_or_else(|err| {
+                eprintln!("Problem in {}: {err}", path.display());
+                process::exit(1);
+            })
+        }
+        None => RuleSet::fizzbuzz(),
+    };
+
+    if let Some(combine) = combine {
+        rules.combine = combine;
+    }
+
+    for line in rules.evaluate(start..=end) {
+        println!("{line}");
+    }
 }
 
 fn control_flow() {
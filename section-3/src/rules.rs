@@ -0,0 +1,321 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+// What happens when a number is divisible by more than one divisor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combine {
+    // only the first matching rule counts, like the `if` / `else if` chain in `control_flow`
+    FirstMatch,
+    // the words of every matching rule are joined, 15 is "FizzBuzz"
+    Concatenate,
+}
+
+impl fmt::Display for Combine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Combine::FirstMatch => "first",
+            Combine::Concatenate => "concatenate",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+impl FromStr for Combine {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Combine, String> {
+        match name.trim().to_lowercase().as_str() {
+            "first" | "first-match" => Ok(Combine::FirstMatch),
+            "concatenate" | "concat" | "all" => Ok(Combine::Concatenate),
+            _ => Err(format!(
+                "unknown mode `{name}`, expected first or concatenate"
+            )),
+        }
+    }
+}
+
+// "3 = Fizz": numbers divisible by 3 are replaced with "Fizz".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    pub divisor: i64,
+    pub word: String,
+}
+
+impl Rule {
+    pub fn matches(&self, number: i64) -> bool {
+        // `i64::MIN % -1` does not fit, the checked version says `None` instead of panicking
+        match number.checked_rem(self.divisor) {
+            Some(remainder) => remainder == 0,
+            None => self.divisor == -1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RuleErrorKind {
+    // the line has no `=`
+    MissingEquals,
+    InvalidDivisor(String),
+    ZeroDivisor,
+    EmptyWord,
+    UnknownMode(String),
+    // the same divisor twice, the second rule could never be the first match
+    DuplicateDivisor(i64),
+    NoRules,
+}
+
+// A problem in a rules file, `line` counts from 1. `NoRules` is about the whole file and has line 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleError {
+    pub line: usize,
+    pub kind: RuleErrorKind,
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let line = self.line;
+
+        match &self.kind {
+            RuleErrorKind::MissingEquals => {
+                write!(
+                    f,
+                    "line {line}: expected `divisor = word` or `mode = first`"
+                )
+            }
+            RuleErrorKind::InvalidDivisor(divisor) => {
+                write!(f, "line {line}: `{divisor}` is not a whole number")
+            }
+            RuleErrorKind::ZeroDivisor => {
+                write!(f, "line {line}: nothing is divisible by 0")
+            }
+            RuleErrorKind::EmptyWord => write!(f, "line {line}: the rule has no word"),
+            RuleErrorKind::UnknownMode(mode) => write!(
+                f,
+                "line {line}: unknown mode `{mode}`, expected first or concatenate"
+            ),
+            RuleErrorKind::DuplicateDivisor(divisor) => {
+                write!(f, "line {line}: there already is a rule for {divisor}")
+            }
+            RuleErrorKind::NoRules => write!(f, "the file has no rules"),
+        }
+    }
+}
+
+impl std::error::Error for RuleError {}
+
+// Divisibility rules in priority order, earlier rules win and come first when words are joined.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleSet {
+    pub rules: Vec<Rule>,
+    pub combine: Combine,
+}
+
+impl RuleSet {
+    // The classic game: 3 is Fizz, 5 is Buzz and 15 is both.
+    pub fn fizzbuzz() -> RuleSet {
+        RuleSet {
+            rules: vec![
+                Rule {
+                    divisor: 3,
+                    word: String::from("Fizz"),
+                },
+                Rule {
+                    divisor: 5,
+                    word: String::from("Buzz"),
+                },
+            ],
+            combine: Combine::Concatenate,
+        }
+    }
+
+    // One rule per line, the line order is the priority order:
+    //
+    //   # comments and blank lines are skipped
+    //   mode = first
+    //   4 = divisible by 4
+    //   3 = divisible by 3
+    //
+    // Without a `mode` line all matching words are concatenated.
+    pub fn parse(text: &str) -> Result<RuleSet, RuleError> {
+        let mut rules: Vec<Rule> = Vec::new();
+        let mut combine = Combine::Concatenate;
+
+        for (index, line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let error = |kind| RuleError {
+                line: line_number,
+                kind,
+            };
+
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(error(RuleErrorKind::MissingEquals));
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            if key.eq_ignore_ascii_case("mode") {
+                combine = value
+                    .parse()
+                    .map_err(|_| error(RuleErrorKind::UnknownMode(value.to_string())))?;
+                continue;
+            }
+
+            let divisor: i64 = key
+                .parse()
+                .map_err(|_| error(RuleErrorKind::InvalidDivisor(key.to_string())))?;
+
+            if divisor == 0 {
+                return Err(error(RuleErrorKind::ZeroDivisor));
+            }
+            if value.is_empty() {
+                return Err(error(RuleErrorKind::EmptyWord));
+            }
+            if rules.iter().any(|rule| rule.divisor == divisor) {
+                return Err(error(RuleErrorKind::DuplicateDivisor(divisor)));
+            }
+
+            rules.push(Rule {
+                divisor,
+                word: value.to_string(),
+            });
+        }
+
+        if rules.is_empty() {
+            return Err(RuleError {
+                line: 0,
+                kind: RuleErrorKind::NoRules,
+            });
+        }
+
+        Ok(RuleSet { rules, combine })
+    }
+
+    // The word for `number`, or the number itself when no rule matches.
+    pub fn apply(&self, number: i64) -> String {
+        let mut matching = self.rules.iter().filter(|rule| rule.matches(number));
+
+        let words: Vec<&str> = match self.combine {
+            Combine::FirstMatch => matching
+                .next()
+                .into_iter()
+                .map(|rule| rule.word.as_str())
+                .collect(),
+            Combine::Concatenate => matching.map(|rule| rule.word.as_str()).collect(),
+        };
+
+        match words.is_empty() {
+            true => number.to_string(),
+            false => words.concat(),
+        }
+    }
+
+    pub fn evaluate(&self, range: RangeInclusive<i64>) -> impl Iterator<Item = String> + '_ {
+        range.map(|number| self.apply(number))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn evaluate(rules: &RuleSet, range: RangeInclusive<i64>) -> Vec<String> {
+        rules.evaluate(range).collect()
+    }
+
+    #[test]
+    fn fizzbuzz() {
+        assert_eq!(
+            evaluate(&RuleSet::fizzbuzz(), 1..=15),
+            [
+                "1", "2", "Fizz", "4", "Buzz", "Fizz", "7", "8", "Fizz", "Buzz", "11", "Fizz",
+                "13", "14", "FizzBuzz"
+            ]
+        );
+    }
+
+    #[test]
+    fn first_match_follows_rule_order() {
+        // the `control_flow` chain: 4 before 3 before 2
+        let rules = RuleSet::parse("mode = first\n4 = four\n3 = three\n2 = two\n").unwrap();
+        assert_eq!(
+            evaluate(&rules, 1..=6),
+            ["1", "two", "three", "four", "5", "three"]
+        );
+        assert_eq!(rules.apply(12), "four");
+
+        // the same rules the other way around
+        let rules = RuleSet::parse("mode = first\n2 = two\n3 = three\n4 = four\n").unwrap();
+        assert_eq!(rules.apply(12), "two");
+        assert_eq!(rules.apply(4), "two");
+    }
+
+    #[test]
+    fn concatenation_follows_rule_order() {
+        let rules = RuleSet::parse("5 = Buzz\n3 = Fizz\n7 = Bazz\n").unwrap();
+
+        assert_eq!(rules.combine, Combine::Concatenate);
+        assert_eq!(rules.apply(15), "BuzzFizz");
+        assert_eq!(rules.apply(105), "BuzzFizzBazz");
+        assert_eq!(rules.apply(21), "FizzBazz");
+        assert_eq!(rules.apply(-10), "Buzz");
+        assert_eq!(rules.apply(0), "BuzzFizzBazz");
+    }
+
+    #[test]
+    fn comments_and_blank_lines() {
+        let text = "# my rules\n\n  3 =  Fizz \nMODE = concatenate\n";
+        let rules = RuleSet::parse(text).unwrap();
+
+        assert_eq!(
+            rules.rules,
+            [Rule {
+                divisor: 3,
+                word: String::from("Fizz")
+            }]
+        );
+    }
+
+    #[test]
+    fn negative_divisors_at_the_edge() {
+        let rule = Rule {
+            divisor: -1,
+            word: String::from("x"),
+        };
+
+        assert!(rule.matches(i64::MIN));
+        assert!(rule.matches(7));
+        assert!(!Rule {
+            divisor: -2,
+            word: String::from("y"),
+        }
+        .matches(i64::MIN + 1));
+    }
+
+    #[test]
+    fn bad_files() {
+        let kind = |text: &str| RuleSet::parse(text).unwrap_err().kind;
+
+        assert_eq!(kind("3 Fizz"), RuleErrorKind::MissingEquals);
+        assert_eq!(
+            kind("three = Fizz"),
+            RuleErrorKind::InvalidDivisor(String::from("three"))
+        );
+        assert_eq!(kind("0 = Zero"), RuleErrorKind::ZeroDivisor);
+        assert_eq!(kind("3 ="), RuleErrorKind::EmptyWord);
+        assert_eq!(
+            kind("mode = last\n3 = Fizz"),
+            RuleErrorKind::UnknownMode(String::from("last"))
+        );
+        assert_eq!(kind("# nothing"), RuleErrorKind::NoRules);
+
+        let err = RuleSet::parse("3 = Fizz\n\n3 = Fuzz").unwrap_err();
+        assert_eq!(err.line, 3);
+        assert_eq!(err.to_string(), "line 3: there already is a rule for 3");
+    }
+}
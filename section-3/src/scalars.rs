@@ -0,0 +1,908 @@
+use std::fmt;
+use std::mem::size_of;
+use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::str::FromStr;
+
+// Every integer and floating-point type of the chapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarType {
+    I8,
+    I16,
+    I32,
+    I64,
+    I128,
+    Isize,
+    U8,
+    U16,
+    U32,
+    U64,
+    U128,
+    Usize,
+    F32,
+    F64,
+}
+
+impl ScalarType {
+    pub const ALL: [ScalarType; 14] = [
+        ScalarType::I8,
+        ScalarType::I16,
+        ScalarType::I32,
+        ScalarType::I64,
+        ScalarType::I128,
+        ScalarType::Isize,
+        ScalarType::U8,
+        ScalarType::U16,
+        ScalarType::U32,
+        ScalarType::U64,
+        ScalarType::U128,
+        ScalarType::Usize,
+        ScalarType::F32,
+        ScalarType::F64,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScalarType::I8 => "i8",
+            ScalarType::I16 => "i16",
+            ScalarType::I32 => "i32",
+            ScalarType::I64 => "i64",
+            ScalarType::I128 => "i128",
+            ScalarType::Isize => "isize",
+            ScalarType::U8 => "u8",
+            ScalarType::U16 => "u16",
+            ScalarType::U32 => "u32",
+            ScalarType::U64 => "u64",
+            ScalarType::U128 => "u128",
+            ScalarType::Usize => "usize",
+            ScalarType::F32 => "f32",
+            ScalarType::F64 => "f64",
+        }
+    }
+
+    pub fn is_float(&self) -> bool {
+        matches!(self, ScalarType::F32 | ScalarType::F64)
+    }
+
+    // in bytes, `isize` and `usize` depend on the machine, 8 on 64-bit ones
+    pub fn size(&self) -> usize {
+        match self {
+            ScalarType::I8 => size_of::<i8>(),
+            ScalarType::I16 => size_of::<i16>(),
+            ScalarType::I32 => size_of::<i32>(),
+            ScalarType::I64 => size_of::<i64>(),
+            ScalarType::I128 => size_of::<i128>(),
+            ScalarType::Isize => size_of::<isize>(),
+            ScalarType::U8 => size_of::<u8>(),
+            ScalarType::U16 => size_of::<u16>(),
+            ScalarType::U32 => size_of::<u32>(),
+            ScalarType::U64 => size_of::<u64>(),
+            ScalarType::U128 => size_of::<u128>(),
+            ScalarType::Usize => size_of::<usize>(),
+            ScalarType::F32 => size_of::<f32>(),
+            ScalarType::F64 => size_of::<f64>(),
+        }
+    }
+
+    // Floats are written with an exponent, f64::MAX has 309 digits otherwise.
+    pub fn min(&self) -> String {
+        match self {
+            ScalarType::I8 => i8::MIN.to_string(),
+            ScalarType::I16 => i16::MIN.to_string(),
+            ScalarType::I32 => i32::MIN.to_string(),
+            ScalarType::I64 => i64::MIN.to_string(),
+            ScalarType::I128 => i128::MIN.to_string(),
+            ScalarType::Isize => isize::MIN.to_string(),
+            ScalarType::U8 => u8::MIN.to_string(),
+            ScalarType::U16 => u16::MIN.to_string(),
+            ScalarType::U32 => u32::MIN.to_string(),
+            ScalarType::U64 => u64::MIN.to_string(),
+            ScalarType::U128 => u128::MIN.to_string(),
+            ScalarType::Usize => usize::MIN.to_string(),
+            ScalarType::F32 => format!("{:e}", f32::MIN),
+            ScalarType::F64 => format!("{:e}", f64::MIN),
+        }
+    }
+
+    pub fn max(&self) -> String {
+        match self {
+            ScalarType::I8 => i8::MAX.to_string(),
+            ScalarType::I16 => i16::MAX.to_string(),
+            ScalarType::I32 => i32::MAX.to_string(),
+            ScalarType::I64 => i64::MAX.to_string(),
+            ScalarType::I128 => i128::MAX.to_string(),
+            ScalarType::Isize => isize::MAX.to_string(),
+            ScalarType::U8 => u8::MAX.to_string(),
+            ScalarType::U16 => u16::MAX.to_string(),
+            ScalarType::U32 => u32::MAX.to_string(),
+            ScalarType::U64 => u64::MAX.to_string(),
+            ScalarType::U128 => u128::MAX.to_string(),
+            ScalarType::Usize => usize::MAX.to_string(),
+            ScalarType::F32 => format!("{:e}", f32::MAX),
+            ScalarType::F64 => format!("{:e}", f64::MAX),
+        }
+    }
+
+    // The answer of `expression` in this type, one cell per `Semantics`.
+    pub fn evaluate(&self, expression: &Expression) -> Vec<String> {
+        match self {
+            ScalarType::I8 => integer_row::<i8>(expression),
+            ScalarType::I16 => integer_row::<i16>(expression),
+            ScalarType::I32 => integer_row::<i32>(expression),
+            ScalarType::I64 => integer_row::<i64>(expression),
+            ScalarType::I128 => integer_row::<i128>(expression),
+            ScalarType::Isize => integer_row::<isize>(expression),
+            ScalarType::U8 => integer_row::<u8>(expression),
+            ScalarType::U16 => integer_row::<u16>(expression),
+            ScalarType::U32 => integer_row::<u32>(expression),
+            ScalarType::U64 => integer_row::<u64>(expression),
+            ScalarType::U128 => integer_row::<u128>(expression),
+            ScalarType::Usize => integer_row::<usize>(expression),
+            ScalarType::F32 => float_row::<f32>(expression),
+            ScalarType::F64 => float_row::<f64>(expression),
+        }
+    }
+}
+
+impl fmt::Display for ScalarType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for ScalarType {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<ScalarType, String> {
+        let name = name.trim().to_lowercase();
+
+        ScalarType::ALL
+            .into_iter()
+            .find(|scalar| scalar.name() == name)
+            .ok_or_else(|| format!("unknown type `{name}`, expected one like i8, u64 or f32"))
+    }
+}
+
+// The ways integer arithmetic can deal with a result that does not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Semantics {
+    // `a + b`: panics in debug builds, wraps around like `Wrapping` in release builds
+    Plain,
+    // `a.checked_add(b)`: `None` instead of a wrong number
+    Checked,
+    // `a.wrapping_add(b)`: starts over from the other end, 255u8 + 1 is 0
+    Wrapping,
+    // `a.saturating_add(b)`: stops at the end, 255u8 + 1 is 255
+    Saturating,
+    // `a.overflowing_add(b)`: the wrapped result and whether it wrapped
+    Overflowing,
+}
+
+impl Semantics {
+    pub const ALL: [Semantics; 5] = [
+        Semantics::Plain,
+        Semantics::Checked,
+        Semantics::Wrapping,
+        Semantics::Saturating,
+        Semantics::Overflowing,
+    ];
+}
+
+impl fmt::Display for Semantics {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Semantics::Plain => "plain (debug)",
+            Semantics::Checked => "checked",
+            Semantics::Wrapping => "wrapping",
+            Semantics::Saturating => "saturating",
+            Semantics::Overflowing => "overflowing",
+        };
+
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Add,
+    Subtract,
+    Multiply,
+    Divide,
+    Remainder,
+}
+
+impl Operator {
+    // the words of the panic message, "attempt to add with overflow"
+    fn verb(&self) -> &'static str {
+        match self {
+            Operator::Add => "add",
+            Operator::Subtract => "subtract",
+            Operator::Multiply => "multiply",
+            Operator::Divide => "divide",
+            Operator::Remainder => "calculate the remainder",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    // kept as text, whether it fits depends on the type it is read as
+    Literal(String),
+    Binary(Box<Node>, Operator, Box<Node>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExpressionErrorKind {
+    ExpectedNumber,
+    UnexpectedCharacter(char),
+    // a `(` that is never closed, `position` is where it was opened
+    Unclosed,
+    // the `(` at `position` is nested deeper than `MAX_DEPTH`
+    TooDeep,
+    TrailingInput,
+}
+
+// Every `(` is one more level of recursion in the parser, this stops far before the stack runs out.
+const MAX_DEPTH: usize = 100;
+
+// A mistake in an expression, `position` counts chars from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpressionError {
+    pub kind: ExpressionErrorKind,
+    pub position: usize,
+}
+
+impl ExpressionError {
+    // Like `PlanError::pointer`, for expressions.
+    pub fn pointer(&self, expression: &str) -> String {
+        format!("{expression}\n{}^", " ".repeat(self.position))
+    }
+}
+
+impl fmt::Display for ExpressionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let column = self.position + 1;
+
+        match &self.kind {
+            ExpressionErrorKind::ExpectedNumber => {
+                write!(f, "expected a number or `(` at column {column}")
+            }
+            ExpressionErrorKind::UnexpectedCharacter(c) => {
+                write!(f, "unexpected `{c}` at column {column}")
+            }
+            ExpressionErrorKind::Unclosed => {
+                write!(f, "the `(` at column {column} is never closed")
+            }
+            ExpressionErrorKind::TooDeep => write!(
+                f,
+                "the `(` at column {column} is nested more than {MAX_DEPTH} deep"
+            ),
+            ExpressionErrorKind::TrailingInput => write!(
+                f,
+                "expected +, -, *, / or % at column {column}, the expression looks complete before it"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExpressionError {}
+
+// Numbers with + - * / % and parentheses, `*` `/` `%` before `+` `-` like in Rust.
+// "-128" is a negative number, written right where a number is expected.
+// "0.1" and "3.4e38" are read by the float types, the integer types cannot read them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expression {
+    root: Node,
+}
+
+impl Expression {
+    pub fn parse(text: &str) -> Result<Expression, ExpressionError> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            next: 0,
+            depth: 0,
+        };
+
+        let root = parser.sum()?;
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            None => Ok(Expression { root }),
+            Some(_) => Err(parser.error(ExpressionErrorKind::TrailingInput)),
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    next: usize,
+    // parentheses open around the current position
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.next).copied()
+    }
+
+    fn error(&self, kind: ExpressionErrorKind) -> ExpressionError {
+        ExpressionError {
+            kind,
+            position: self.next,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next += 1;
+        }
+    }
+
+    // digits and `_` in a row, true when there was at least one digit
+    fn digits(&mut self) -> bool {
+        let mut any = false;
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_digit() || *c == '_') {
+            any |= c.is_ascii_digit();
+            self.next += 1;
+        }
+
+        any
+    }
+
+    // terms joined by + and -
+    fn sum(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.product()?;
+
+        loop {
+            self.skip_whitespace();
+            let operator = match self.peek() {
+                Some('+') => Operator::Add,
+                Some('-') => Operator::Subtract,
+                _ => return Ok(node),
+            };
+            self.next += 1;
+
+            node = Node::Binary(Box::new(node), operator, Box::new(self.product()?));
+        }
+    }
+
+    // operands joined by *, / and %
+    fn product(&mut self) -> Result<Node, ExpressionError> {
+        let mut node = self.operand()?;
+
+        loop {
+            self.skip_whitespace();
+            let operator = match self.peek() {
+                Some('*') => Operator::Multiply,
+                Some('/') => Operator::Divide,
+                Some('%') => Operator::Remainder,
+                _ => return Ok(node),
+            };
+            self.next += 1;
+
+            node = Node::Binary(Box::new(node), operator, Box::new(self.operand()?));
+        }
+    }
+
+    // a number, maybe negative, or a whole expression in parentheses
+    fn operand(&mut self) -> Result<Node, ExpressionError> {
+        self.skip_whitespace();
+
+        if self.peek() == Some('(') {
+            let open = self.next;
+            if self.depth == MAX_DEPTH {
+                return Err(self.error(ExpressionErrorKind::TooDeep));
+            }
+            self.next += 1;
+
+            self.depth += 1;
+            let node = self.sum()?;
+            self.depth -= 1;
+            self.skip_whitespace();
+            if self.peek() != Some(')') {
+                return Err(ExpressionError {
+                    kind: ExpressionErrorKind::Unclosed,
+                    position: open,
+                });
+            }
+            self.next += 1;
+
+            return Ok(node);
+        }
+
+        let start = self.next;
+        if self.peek() == Some('-') {
+            self.next += 1;
+        }
+        // `1_000` like in Rust source
+        self.digits();
+
+        // "0.1" and "3.4e38" are float literals, an integer type reports them as not fitting
+        if self.next > start && self.peek() == Some('.') {
+            self.next += 1;
+            self.digits();
+        }
+        if self.next > start && matches!(self.peek(), Some('e' | 'E')) {
+            let before = self.next;
+            self.next += 1;
+            if matches!(self.peek(), Some('+' | '-')) {
+                self.next += 1;
+            }
+
+            // an `e` without digits after it is not part of the number
+            if !self.digits() {
+                self.next = before;
+            }
+        }
+
+        let text: String = self.chars[start..self.next]
+            .iter()
+            .filter(|c| **c != '_')
+            .collect();
+
+        if !text.chars().any(|c| c.is_ascii_digit()) {
+            self.next = start;
+            return Err(match self.peek() {
+                None | Some('-') => self.error(ExpressionErrorKind::ExpectedNumber),
+                Some(c) if "+*/%)".contains(c) => self.error(ExpressionErrorKind::ExpectedNumber),
+                Some(c) => self.error(ExpressionErrorKind::UnexpectedCharacter(c)),
+            });
+        }
+
+        Ok(Node::Literal(text))
+    }
+}
+
+// The integer methods this explorer shows, implemented for every integer type by `integer!`.
+trait Integer: Copy + fmt::Display + FromStr + PartialEq {
+    const ZERO: Self;
+
+    fn checked(self, operator: Operator, other: Self) -> Option<Self>;
+    fn wrapping(self, operator: Operator, other: Self) -> Self;
+    fn saturating(self, operator: Operator, other: Self) -> Self;
+    fn overflowing(self, operator: Operator, other: Self) -> (Self, bool);
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const ZERO: $t = 0;
+
+                fn checked(self, operator: Operator, other: $t) -> Option<$t> {
+                    match operator {
+                        Operator::Add => self.checked_add(other),
+                        Operator::Subtract => self.checked_sub(other),
+                        Operator::Multiply => self.checked_mul(other),
+                        Operator::Divide => self.checked_div(other),
+                        Operator::Remainder => self.checked_rem(other),
+                    }
+                }
+
+                fn wrapping(self, operator: Operator, other: $t) -> $t {
+                    match operator {
+                        Operator::Add => self.wrapping_add(other),
+                        Operator::Subtract => self.wrapping_sub(other),
+                        Operator::Multiply => self.wrapping_mul(other),
+                        Operator::Divide => self.wrapping_div(other),
+                        Operator::Remainder => self.wrapping_rem(other),
+                    }
+                }
+
+                fn saturating(self, operator: Operator, other: $t) -> $t {
+                    match operator {
+                        Operator::Add => self.saturating_add(other),
+                        Operator::Subtract => self.saturating_sub(other),
+                        Operator::Multiply => self.saturating_mul(other),
+                        Operator::Divide => self.saturating_div(other),
+                        // there is no `saturating_rem`, the only overflow is MIN % -1 which is 0 anyway
+                        Operator::Remainder => self.wrapping_rem(other),
+                    }
+                }
+
+                fn overflowing(self, operator: Operator, other: $t) -> ($t, bool) {
+                    match operator {
+                        Operator::Add => self.overflowing_add(other),
+                        Operator::Subtract => self.overflowing_sub(other),
+                        Operator::Multiply => self.overflowing_mul(other),
+                        Operator::Divide => self.overflowing_div(other),
+                        Operator::Remainder => self.overflowing_rem(other),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// Why an evaluation stopped before it had a number.
+enum Stop {
+    // `checked_*` returned `None`
+    Nothing,
+    Panic(String),
+    // a literal that is not in range of the type, e.g. 300 as u8
+    OutOfRange(String),
+}
+
+// `Ok` has the result and whether any step on the way overflowed.
+fn integer_value<T: Integer>(node: &Node, semantics: Semantics) -> Result<(T, bool), Stop> {
+    let (left, operator, right) = match node {
+        Node::Literal(text) => {
+            return text
+                .parse()
+                .map(|value| (value, false))
+                .map_err(|_| Stop::OutOfRange(text.clone()))
+        }
+        Node::Binary(left, operator, right) => (left, *operator, right),
+    };
+
+    let (left, left_overflowed) = integer_value::<T>(left, semantics)?;
+    let (right, right_overflowed) = integer_value::<T>(right, semantics)?;
+    let overflowed = left_overflowed || right_overflowed;
+
+    // dividing by zero panics whatever the method, only `checked_*` turns it into `None`
+    let by_zero = right == T::ZERO && matches!(operator, Operator::Divide | Operator::Remainder);
+    if by_zero && semantics != Semantics::Checked {
+        return Err(Stop::Panic(format!(
+            "attempt to {} by zero",
+            operator.verb()
+        )));
+    }
+
+    match semantics {
+        Semantics::Plain => match left.overflowing(operator, right) {
+            (value, false) => Ok((value, overflowed)),
+            (_, true) => Err(Stop::Panic(format!(
+                "attempt to {} with overflow",
+                operator.verb()
+            ))),
+        },
+        Semantics::Checked => left
+            .checked(operator, right)
+            .map(|value| (value, false))
+            .ok_or(Stop::Nothing),
+        Semantics::Wrapping => Ok((left.wrapping(operator, right), false)),
+        Semantics::Saturating => Ok((left.saturating(operator, right), false)),
+        Semantics::Overflowing => {
+            let (value, now) = left.overflowing(operator, right);
+            Ok((value, overflowed || now))
+        }
+    }
+}
+
+fn integer_row<T: Integer>(expression: &Expression) -> Vec<String> {
+    Semantics::ALL
+        .iter()
+        .map(
+            |semantics| match integer_value::<T>(&expression.root, *semantics) {
+                Ok((value, _)) if *semantics == Semantics::Checked => format!("Some({value})"),
+                Ok((value, overflowed)) if *semantics == Semantics::Overflowing => {
+                    format!("({value}, {overflowed})")
+                }
+                Ok((value, _)) => value.to_string(),
+                Err(Stop::Nothing) => String::from("None"),
+                Err(Stop::Panic(message)) => format!("panic: {message}"),
+                Err(Stop::OutOfRange(text)) => format!("{text} does not fit"),
+            },
+        )
+        .collect()
+}
+
+// Floats never overflow into a panic, they go to inf, and 0.0 / 0.0 is NaN.
+fn float_value<T>(node: &Node) -> Result<T, String>
+where
+    T: Copy
+        + FromStr
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>,
+{
+    match node {
+        Node::Literal(text) => text.parse().map_err(|_| format!("{text} does not fit")),
+        Node::Binary(left, operator, right) => {
+            let (left, right) = (float_value::<T>(left)?, float_value::<T>(right)?);
+
+            Ok(match operator {
+                Operator::Add => left + right,
+                Operator::Subtract => left - right,
+                Operator::Multiply => left * right,
+                Operator::Divide => left / right,
+                Operator::Remainder => left % right,
+            })
+        }
+    }
+}
+
+// Floats have no checked, wrapping or saturating methods, only the plain operators.
+// Wider plain floats are written like `1e301` instead.
+const MAX_FLOAT_WIDTH: usize = 20;
+
+fn float_row<T>(expression: &Expression) -> Vec<String>
+where
+    T: Copy
+        + fmt::Display
+        + fmt::LowerExp
+        + FromStr
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>,
+{
+    // `Display` writes every digit, 1e300 would fill 301 columns
+    let plain = float_value::<T>(&expression.root)
+        .map(|value| match value.to_string() {
+            text if text.len() > MAX_FLOAT_WIDTH => format!("{value:e}"),
+            text => text,
+        })
+        .unwrap_or_else(|err| err);
+
+    Semantics::ALL
+        .iter()
+        .map(|semantics| match semantics {
+            Semantics::Plain => plain.clone(),
+            _ => String::from("-"),
+        })
+        .collect()
+}
+
+// Size, min and max of every type.
+pub fn types_table() -> String {
+    let header = ["type", "bytes", "bits", "min", "max"];
+    let rows: Vec<Vec<String>> = ScalarType::ALL
+        .iter()
+        .map(|scalar| {
+            vec![
+                scalar.to_string(),
+                scalar.size().to_string(),
+                (scalar.size() * 8).to_string(),
+                scalar.min(),
+                scalar.max(),
+            ]
+        })
+        .collect();
+
+    table(&header, &rows)
+}
+
+// `expression` evaluated in each of `types`, one column per `Semantics`.
+pub fn evaluation_table(expression: &Expression, types: &[ScalarType]) -> String {
+    let semantics: Vec<String> = Semantics::ALL.iter().map(|s| s.to_string()).collect();
+    let header: Vec<&str> = ["type"]
+        .into_iter()
+        .chain(semantics.iter().map(String::as_str))
+        .collect();
+
+    let rows: Vec<Vec<String>> = types
+        .iter()
+        .map(|scalar| {
+            let mut row = vec![scalar.to_string()];
+            row.extend(scalar.evaluate(expression));
+            row
+        })
+        .collect();
+
+    table(&header, &rows)
+}
+
+// Left aligned columns with a line under the header.
+fn table(header: &[&str], rows: &[Vec<String>]) -> String {
+    let widths: Vec<usize> = header
+        .iter()
+        .enumerate()
+        .map(|(column, name)| {
+            rows.iter()
+                .map(|row| row[column].chars().count())
+                .chain([name.chars().count()])
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |cells: Vec<&str>| {
+        cells
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ")
+            .trim_end()
+            .to_string()
+    };
+
+    let dashes: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+
+    let mut lines = vec![line(header.to_vec())];
+    lines.push(line(dashes.iter().map(String::as_str).collect()));
+    lines.extend(
+        rows.iter()
+            .map(|row| line(row.iter().map(String::as_str).collect())),
+    );
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(scalar: ScalarType, expression: &str) -> Vec<String> {
+        scalar.evaluate(&Expression::parse(expression).unwrap())
+    }
+
+    #[test]
+    fn sizes_and_ranges() {
+        assert_eq!(ScalarType::U8.size(), 1);
+        assert_eq!(ScalarType::I128.size(), 16);
+        assert_eq!(ScalarType::F32.size(), 4);
+        assert_eq!(ScalarType::I8.min(), "-128");
+        assert_eq!(ScalarType::U16.max(), "65535");
+        assert_eq!(ScalarType::F32.max(), "3.4028235e38");
+
+        let table = types_table();
+        assert_eq!(table.lines().count(), 2 + ScalarType::ALL.len());
+        let u128_row: Vec<&str> = table
+            .lines()
+            .find(|line| line.starts_with("u128 "))
+            .unwrap()
+            .split_whitespace()
+            .collect();
+        assert_eq!(
+            u128_row,
+            [
+                "u128",
+                "16",
+                "128",
+                "0",
+                "340282366920938463463374607431768211455"
+            ]
+        );
+    }
+
+    #[test]
+    fn overflow_side_by_side() {
+        assert_eq!(
+            row(ScalarType::U8, "200 + 100"),
+            [
+                "panic: attempt to add with overflow",
+                "None",
+                "44",
+                "255",
+                "(44, true)"
+            ]
+        );
+        assert_eq!(
+            row(ScalarType::I32, "200 + 100"),
+            ["300", "Some(300)", "300", "300", "(300, false)"]
+        );
+        assert_eq!(
+            row(ScalarType::I8, "-128 * -1"),
+            [
+                "panic: attempt to multiply with overflow",
+                "None",
+                "-128",
+                "127",
+                "(-128, true)"
+            ]
+        );
+        assert_eq!(
+            row(ScalarType::U32, "0 - 1"),
+            [
+                "panic: attempt to subtract with overflow",
+                "None",
+                "4294967295",
+                "0",
+                "(4294967295, true)"
+            ]
+        );
+    }
+
+    #[test]
+    fn overflow_in_the_middle_is_remembered() {
+        // 250 + 10 wraps to 4, taking 10 off gives 250 again
+        assert_eq!(
+            row(ScalarType::U8, "250 + 10 - 10"),
+            [
+                "panic: attempt to add with overflow",
+                "None",
+                "250",
+                "245",
+                "(250, true)"
+            ]
+        );
+    }
+
+    #[test]
+    fn division_by_zero_and_literals_out_of_range() {
+        assert_eq!(
+            row(ScalarType::I32, "1 / (2 - 2)"),
+            [
+                "panic: attempt to divide by zero",
+                "None",
+                "panic: attempt to divide by zero",
+                "panic: attempt to divide by zero",
+                "panic: attempt to divide by zero"
+            ]
+        );
+        assert_eq!(row(ScalarType::U8, "300 - 100")[1], "300 does not fit");
+        assert_eq!(row(ScalarType::U8, "-1 + 1")[0], "-1 does not fit");
+    }
+
+    #[test]
+    fn floats_use_plain_operators() {
+        assert_eq!(row(ScalarType::F64, "7 / 2"), ["3.5", "-", "-", "-", "-"]);
+        assert_eq!(row(ScalarType::F32, "1 / 0")[0], "inf");
+        assert_eq!(row(ScalarType::F64, "0 / 0")[0], "NaN");
+    }
+
+    #[test]
+    fn float_literals() {
+        assert_eq!(row(ScalarType::F64, "0.1 + 0.2")[0], "0.30000000000000004");
+        assert_eq!(row(ScalarType::F32, "0.1 + 0.2")[0], "0.3");
+        assert_eq!(row(ScalarType::F32, "3.4e38 * 10")[0], "inf");
+        assert_eq!(row(ScalarType::F64, "-1.5E-3 * 2")[0], "-0.003");
+        assert_eq!(row(ScalarType::F64, "1e300 * 10")[0], "1e301");
+        assert_eq!(row(ScalarType::F64, "1e-300 / 10")[0], "1e-301");
+        assert_eq!(row(ScalarType::F64, "1e19")[0], "10000000000000000000");
+        assert_eq!(
+            row(ScalarType::I32, "0.1 + 0.2"),
+            vec!["0.1 does not fit"; 5]
+        );
+        assert_eq!(row(ScalarType::U64, "1e3")[0], "1e3 does not fit");
+
+        let error = Expression::parse("2e + 1").unwrap_err();
+        assert_eq!(
+            error,
+            ExpressionError {
+                kind: ExpressionErrorKind::TrailingInput,
+                position: 1
+            }
+        );
+    }
+
+    #[test]
+    fn precedence_and_parentheses() {
+        assert_eq!(row(ScalarType::I32, "2 + 3 * 4")[0], "14");
+        assert_eq!(row(ScalarType::I32, "(2 + 3) * 4")[0], "20");
+        assert_eq!(row(ScalarType::I32, "10 - 4 - 3")[0], "3");
+        assert_eq!(row(ScalarType::I32, "17 % 5 * 1_000")[0], "2000");
+    }
+
+    #[test]
+    fn expression_errors() {
+        let error = |text: &str| Expression::parse(text).unwrap_err();
+
+        assert_eq!(
+            error("200 + x"),
+            ExpressionError {
+                kind: ExpressionErrorKind::UnexpectedCharacter('x'),
+                position: 6
+            }
+        );
+        assert_eq!(error("200 +").kind, ExpressionErrorKind::ExpectedNumber);
+        assert_eq!(error("(1 + 2").kind, ExpressionErrorKind::Unclosed);
+        assert_eq!(error("1 2").kind, ExpressionErrorKind::TrailingInput);
+        assert_eq!(error("1 + x").pointer("1 + x"), "1 + x\n    ^");
+
+        let deep = "(".repeat(20_000) + "1" + &")".repeat(20_000);
+        assert_eq!(
+            error(&deep),
+            ExpressionError {
+                kind: ExpressionErrorKind::TooDeep,
+                position: MAX_DEPTH
+            }
+        );
+        let fine = "(".repeat(MAX_DEPTH) + "1" + &")".repeat(MAX_DEPTH);
+        assert!(Expression::parse(&fine).is_ok());
+    }
+
+    #[test]
+    fn type_names() {
+        for scalar in ScalarType::ALL {
+            assert_eq!(scalar.to_string().parse(), Ok(scalar));
+        }
+        assert!("u7".parse::<ScalarType>().is_err());
+    }
+}
@@ -0,0 +1,810 @@
+use std::cell::Cell;
+use std::fmt;
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, Instant};
+
+// How often the countdown looks at the clock, and at pause and resume commands.
+pub const TICK: Duration = Duration::from_secs(1);
+
+// Where the timer gets the time from. The real clock sleeps, tests use `ManualClock`
+// which only pretends to, so a ten minute plan is checked in no time.
+pub trait Clock {
+    // time since the clock was created
+    fn now(&self) -> Duration;
+    fn sleep(&self, duration: Duration);
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct SystemClock {
+    start: Instant,
+}
+
+impl Default for SystemClock {
+    fn default() -> SystemClock {
+        SystemClock {
+            start: Instant::now(),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        thread::sleep(duration);
+    }
+}
+
+// A clock that only moves when it sleeps or is told to.
+#[derive(Debug, Default)]
+pub struct ManualClock {
+    now: Cell<Duration>,
+}
+
+impl ManualClock {
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Duration {
+        self.now.get()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        self.advance(duration);
+    }
+}
+
+// One line typed while the timer runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Pause,
+    Resume,
+    // just pressing enter pauses a running timer and resumes a paused one
+    Toggle,
+    Quit,
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(text: &str) -> Result<Command, String> {
+        match text.trim().to_lowercase().as_str() {
+            "" => Ok(Command::Toggle),
+            "p" | "pause" => Ok(Command::Pause),
+            "r" | "resume" => Ok(Command::Resume),
+            "q" | "quit" | "stop" => Ok(Command::Quit),
+            other => Err(format!(
+                "unknown command `{other}`, press enter to pause or resume, or type q to stop"
+            )),
+        }
+    }
+}
+
+// A plan is a list of steps: a named phase that lasts a while, or steps repeated a number of times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Step {
+    Phase { name: String, duration: Duration },
+    Repeat { times: u32, steps: Vec<Step> },
+}
+
+// The schedule has one entry per phase, so a plan like "4000000000x(a 1s)" is refused
+// before it is unrolled into more entries than memory can hold.
+pub const MAX_PHASES: u64 = 100_000;
+
+// Repeats are parsed, counted and unrolled recursively, one level per `(`.
+pub const MAX_DEPTH: usize = 50;
+
+impl Step {
+    // How many phases this step runs, repeats included.
+    pub fn phases(&self) -> u64 {
+        match self {
+            Step::Phase { .. } => 1,
+            Step::Repeat { times, steps } => steps
+                .iter()
+                .fold(0u64, |sum, step| sum.saturating_add(step.phases()))
+                .saturating_mul(u64::from(*times)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanErrorKind {
+    Empty,
+    ExpectedName,
+    // a phase name without a duration after it
+    ExpectedDuration,
+    InvalidDuration(String),
+    ZeroDuration,
+    ZeroRepeat,
+    // a `(` that is never closed, `position` is where it was opened
+    Unclosed,
+    UnexpectedCharacter(char),
+    // the step at `position` would take the plan past `MAX_PHASES`
+    TooManyPhases,
+    // the `(` at `position` is nested deeper than `MAX_DEPTH`
+    TooDeep,
+}
+
+// A mistake in a plan, `position` counts chars from 0.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanError {
+    pub kind: PlanErrorKind,
+    pub position: usize,
+}
+
+impl PlanError {
+    // `plan` and a line with `^` at `position`.
+    pub fn pointer(&self, plan: &str) -> String {
+        format!("{plan}\n{}^", " ".repeat(self.position))
+    }
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let column = self.position + 1;
+
+        match &self.kind {
+            PlanErrorKind::Empty => write!(f, "the plan has no phases"),
+            PlanErrorKind::ExpectedName => {
+                write!(
+                    f,
+                    "expected a phase name or a repeat like `3x(` at column {column}"
+                )
+            }
+            PlanErrorKind::ExpectedDuration => {
+                write!(f, "expected a duration like 30s or 2m at column {column}")
+            }
+            PlanErrorKind::InvalidDuration(text) => write!(
+                f,
+                "`{text}` at column {column} is not a duration, use something like 45s, 2m or 1m30s"
+            ),
+            PlanErrorKind::ZeroDuration => write!(f, "the phase at column {column} takes no time"),
+            PlanErrorKind::ZeroRepeat => write!(f, "repeating 0 times at column {column}"),
+            PlanErrorKind::Unclosed => write!(f, "the `(` at column {column} is never closed"),
+            PlanErrorKind::UnexpectedCharacter(c) => {
+                write!(f, "unexpected `{c}` at column {column}")
+            }
+            PlanErrorKind::TooManyPhases => write!(
+                f,
+                "the step at column {column} takes the plan past {MAX_PHASES} phases"
+            ),
+            PlanErrorKind::TooDeep => write!(
+                f,
+                "the `(` at column {column} is nested more than {MAX_DEPTH} repeats deep"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlanError {}
+
+// Which time round a phase is in, for every repeat around it, outermost first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub current: u32,
+    pub of: u32,
+}
+
+// A phase from the plan with the repeats unrolled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scheduled {
+    pub name: String,
+    pub duration: Duration,
+    pub rounds: Vec<Round>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan {
+    pub steps: Vec<Step>,
+}
+
+impl Plan {
+    // "warmup 1m, 3x(work 30s, rest 10s), cooldown 2m"
+    // Repeats can be nested: "2x(3x(work 20s, rest 10s), break 1m)".
+    pub fn parse(text: &str) -> Result<Plan, PlanError> {
+        let mut parser = Parser {
+            chars: text.chars().collect(),
+            next: 0,
+            depth: 0,
+        };
+
+        let steps = parser.steps()?;
+
+        parser.skip_whitespace();
+        match parser.peek() {
+            None if steps.is_empty() => Err(parser.error(PlanErrorKind::Empty)),
+            None => Ok(Plan { steps }),
+            Some(c) => Err(parser.error(PlanErrorKind::UnexpectedCharacter(c))),
+        }
+    }
+
+    // Every phase in the order it runs.
+    pub fn schedule(&self) -> Vec<Scheduled> {
+        fn unroll(steps: &[Step], rounds: &mut Vec<Round>, schedule: &mut Vec<Scheduled>) {
+            for step in steps {
+                match step {
+                    Step::Phase { name, duration } => schedule.push(Scheduled {
+                        name: name.clone(),
+                        duration: *duration,
+                        rounds: rounds.clone(),
+                    }),
+                    Step::Repeat { times, steps } => {
+                        for current in 1..=*times {
+                            rounds.push(Round {
+                                current,
+                                of: *times,
+                            });
+                            unroll(steps, rounds, schedule);
+                            rounds.pop();
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut schedule = Vec::new();
+        unroll(&self.steps, &mut Vec::new(), &mut schedule);
+        schedule
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    next: usize,
+    // repeats open around the current position
+    depth: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.next).copied()
+    }
+
+    fn error(&self, kind: PlanErrorKind) -> PlanError {
+        PlanError {
+            kind,
+            position: self.next,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next += 1;
+        }
+    }
+
+    // letters, digits and `-` or `_` in a row
+    fn word(&mut self) -> String {
+        let start = self.next;
+        while self
+            .peek()
+            .is_some_and(|c| c.is_alphanumeric() || c == '-' || c == '_')
+        {
+            self.next += 1;
+        }
+
+        self.chars[start..self.next].iter().collect()
+    }
+
+    // steps separated by commas, up to the end or a `)`
+    fn steps(&mut self) -> Result<Vec<Step>, PlanError> {
+        let mut steps = Vec::new();
+        let mut phases = 0u64;
+
+        loop {
+            self.skip_whitespace();
+            if steps.is_empty() && matches!(self.peek(), None | Some(')')) {
+                return Ok(steps);
+            }
+
+            let start = self.next;
+            let step = self.step()?;
+
+            // checked at every level, so a repeat is counted before an outer one multiplies it
+            phases = phases.saturating_add(step.phases());
+            if phases > MAX_PHASES {
+                return Err(PlanError {
+                    kind: PlanErrorKind::TooManyPhases,
+                    position: start,
+                });
+            }
+
+            steps.push(step);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.next += 1,
+                _ => return Ok(steps),
+            }
+        }
+    }
+
+    fn step(&mut self) -> Result<Step, PlanError> {
+        let start = self.next;
+        let word = self.word();
+
+        if word.is_empty() {
+            return Err(self.error(PlanErrorKind::ExpectedName));
+        }
+
+        // "3x(" is a repeat, anything else is the name of a phase
+        let repeat = word
+            .strip_suffix(['x', 'X'])
+            .and_then(|times| times.parse::<u32>().ok());
+
+        if let Some(times) = repeat {
+            self.skip_whitespace();
+
+            if self.peek() == Some('(') {
+                let open = self.next;
+                if self.depth == MAX_DEPTH {
+                    return Err(self.error(PlanErrorKind::TooDeep));
+                }
+                self.next += 1;
+
+                self.depth += 1;
+                let steps = self.steps()?;
+                self.depth -= 1;
+                self.skip_whitespace();
+                if self.peek() != Some(')') {
+                    return Err(PlanError {
+                        kind: PlanErrorKind::Unclosed,
+                        position: open,
+                    });
+                }
+                self.next += 1;
+
+                if times == 0 {
+                    return Err(PlanError {
+                        kind: PlanErrorKind::ZeroRepeat,
+                        position: start,
+                    });
+                }
+                if steps.is_empty() {
+                    return Err(PlanError {
+                        kind: PlanErrorKind::Empty,
+                        position: open,
+                    });
+                }
+
+                return Ok(Step::Repeat { times, steps });
+            }
+        }
+
+        self.skip_whitespace();
+        let duration_start = self.next;
+        let text = self.word();
+        if text.is_empty() {
+            return Err(self.error(PlanErrorKind::ExpectedDuration));
+        }
+
+        let duration = parse_duration(&text).ok_or_else(|| PlanError {
+            kind: PlanErrorKind::InvalidDuration(text.clone()),
+            position: duration_start,
+        })?;
+
+        if duration.is_zero() {
+            return Err(PlanError {
+                kind: PlanErrorKind::ZeroDuration,
+                position: duration_start,
+            });
+        }
+
+        Ok(Step::Phase {
+            name: word,
+            duration,
+        })
+    }
+}
+
+// "90", "90s", "2m", "1m30s" or "1h".
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    if let Ok(seconds) = text.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let mut seconds: u64 = 0;
+    let mut number = String::new();
+
+    for c in text.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+
+        let value: u64 = number.parse().ok()?;
+        seconds = seconds.checked_add(value.checked_mul(unit)?)?;
+        number.clear();
+    }
+
+    // digits without a unit at the end, like "1m30"
+    if !number.is_empty() {
+        return None;
+    }
+
+    Some(Duration::from_secs(seconds))
+}
+
+// 65 seconds is "1:05", an hour and a bit is "1:02:03".
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs();
+
+    match seconds / 3600 {
+        0 => format!("{}:{:02}", seconds / 60, seconds % 60),
+        hours => format!("{hours}:{:02}:{:02}", seconds / 60 % 60, seconds % 60),
+    }
+}
+
+// What happened while running, for whoever shows the countdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Event<'a> {
+    Started(&'a Scheduled),
+    Tick { remaining: Duration },
+    Paused,
+    Resumed,
+    // the phase ran out, or was cut short when the timer is stopped
+    Ended(&'a Scheduled),
+}
+
+// Time spent in every phase with the same name, in the order the names first came up.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhaseTotal {
+    pub name: String,
+    pub runs: usize,
+    pub elapsed: Duration,
+    pub paused: Duration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Summary {
+    pub phases: Vec<PhaseTotal>,
+    // false when the timer was stopped before the plan ran out
+    pub completed: bool,
+}
+
+impl Summary {
+    pub fn elapsed(&self) -> Duration {
+        self.phases.iter().map(|phase| phase.elapsed).sum()
+    }
+
+    pub fn paused(&self) -> Duration {
+        self.phases.iter().map(|phase| phase.paused).sum()
+    }
+
+    fn add(&mut self, name: &str, elapsed: Duration, paused: Duration) {
+        let total = match self.phases.iter().position(|phase| phase.name == name) {
+            Some(index) => &mut self.phases[index],
+            None => {
+                self.phases.push(PhaseTotal {
+                    name: name.to_string(),
+                    runs: 0,
+                    elapsed: Duration::ZERO,
+                    paused: Duration::ZERO,
+                });
+                self.phases.last_mut().unwrap()
+            }
+        };
+
+        total.runs += 1;
+        total.elapsed += elapsed;
+        total.paused += paused;
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let width = self
+            .phases
+            .iter()
+            .map(|phase| phase.name.chars().count())
+            .chain(["total".len()])
+            .max()
+            .unwrap_or(0);
+
+        for phase in &self.phases {
+            writeln!(
+                f,
+                "{:width$}  {}x  {:>8}  paused {}",
+                phase.name,
+                phase.runs,
+                format_duration(phase.elapsed),
+                format_duration(phase.paused)
+            )?;
+        }
+
+        write!(
+            f,
+            "{:width$}      {:>8}  paused {}",
+            "total",
+            format_duration(self.elapsed()),
+            format_duration(self.paused())
+        )?;
+
+        if !self.completed {
+            write!(f, "\nstopped before the end")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Counts down every phase of a plan, one after the other.
+#[derive(Debug, Clone)]
+pub struct Timer {
+    pub schedule: Vec<Scheduled>,
+    pub tick: Duration,
+}
+
+impl Timer {
+    pub fn new(plan: &Plan) -> Timer {
+        Timer {
+            schedule: plan.schedule(),
+            tick: TICK,
+        }
+    }
+
+    // `poll` is asked for commands once per tick and `report` hears about everything that happens.
+    // Time is measured with `clock.now()`, so a sleep that runs long is not lost.
+    pub fn run(
+        &self,
+        clock: &impl Clock,
+        mut poll: impl FnMut() -> Option<Command>,
+        mut report: impl FnMut(Event),
+    ) -> Summary {
+        let mut summary = Summary {
+            phases: Vec::new(),
+            completed: true,
+        };
+        let mut paused = false;
+
+        // the same label trick as `'counting_up` in `control_flow`, quitting leaves both loops
+        'phases: for phase in &self.schedule {
+            report(Event::Started(phase));
+
+            let mut remaining = phase.duration;
+            let mut paused_for = Duration::ZERO;
+
+            while !remaining.is_zero() {
+                while let Some(command) = poll() {
+                    let pause = match command {
+                        Command::Pause => true,
+                        Command::Resume => false,
+                        Command::Toggle => !paused,
+                        Command::Quit => {
+                            summary.add(&phase.name, phase.duration - remaining, paused_for);
+                            summary.completed = false;
+                            report(Event::Ended(phase));
+                            break 'phases;
+                        }
+                    };
+
+                    if pause != paused {
+                        paused = pause;
+                        report(if paused {
+                            Event::Paused
+                        } else {
+                            Event::Resumed
+                        });
+                    }
+                }
+
+                let before = clock.now();
+                clock.sleep(match paused {
+                    true => self.tick,
+                    false => self.tick.min(remaining),
+                });
+                let spent = clock.now().saturating_sub(before);
+
+                if paused {
+                    paused_for += spent;
+                } else {
+                    remaining = remaining.saturating_sub(spent);
+                    report(Event::Tick { remaining });
+                }
+            }
+
+            summary.add(&phase.name, phase.duration, paused_for);
+            report(Event::Ended(phase));
+        }
+
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seconds(seconds: u64) -> Duration {
+        Duration::from_secs(seconds)
+    }
+
+    fn names(plan: &str) -> Vec<String> {
+        Plan::parse(plan)
+            .unwrap()
+            .schedule()
+            .into_iter()
+            .map(|phase| phase.name)
+            .collect()
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("90"), Some(seconds(90)));
+        assert_eq!(parse_duration("45s"), Some(seconds(45)));
+        assert_eq!(parse_duration("1m30s"), Some(seconds(90)));
+        assert_eq!(parse_duration("1h"), Some(seconds(3600)));
+        assert_eq!(parse_duration("1m30"), None);
+        assert_eq!(parse_duration("fast"), None);
+
+        assert_eq!(format_duration(seconds(65)), "1:05");
+        assert_eq!(format_duration(seconds(3723)), "1:02:03");
+    }
+
+    #[test]
+    fn nested_repeats_unroll_in_order() {
+        assert_eq!(
+            names("warmup 1m, 2x(work 30s, rest 10s), cooldown 2m"),
+            ["warmup", "work", "rest", "work", "rest", "cooldown"]
+        );
+        assert_eq!(names("2x(a 1s, 2x(b 1s))"), ["a", "b", "b", "a", "b", "b"]);
+
+        let schedule = Plan::parse("2x(a 1s, 3x(b 1s))").unwrap().schedule();
+        assert_eq!(
+            schedule[7].rounds,
+            [Round { current: 2, of: 2 }, Round { current: 3, of: 3 }]
+        );
+    }
+
+    #[test]
+    fn plan_errors_point_at_the_problem() {
+        let error = |text: &str| Plan::parse(text).unwrap_err();
+
+        assert_eq!(error("").kind, PlanErrorKind::Empty);
+        assert_eq!(error("work").kind, PlanErrorKind::ExpectedDuration);
+        assert_eq!(
+            error("work 30s, rest ten").kind,
+            PlanErrorKind::InvalidDuration(String::from("ten"))
+        );
+        assert_eq!(
+            error("work 30s, rest ten").pointer("work 30s, rest ten"),
+            "work 30s, rest ten\n               ^"
+        );
+        assert_eq!(error("work 0s").kind, PlanErrorKind::ZeroDuration);
+        assert_eq!(error("0x(work 1s)").kind, PlanErrorKind::ZeroRepeat);
+        assert_eq!(
+            error("work 1s, 3x(rest 1s"),
+            PlanError {
+                kind: PlanErrorKind::Unclosed,
+                position: 11
+            }
+        );
+        assert_eq!(
+            error("work 1s)").kind,
+            PlanErrorKind::UnexpectedCharacter(')')
+        );
+        assert_eq!(error("work 1s,").kind, PlanErrorKind::ExpectedName);
+        assert_eq!(
+            error("warmup 1m, 4000000000x(a 1s)"),
+            PlanError {
+                kind: PlanErrorKind::TooManyPhases,
+                position: 11
+            }
+        );
+        assert_eq!(
+            error("1000x(a 1s, 1000x(b 1s))").kind,
+            PlanErrorKind::TooManyPhases
+        );
+        assert!(Plan::parse("1000x(a 1s, 99x(b 1s))").is_ok());
+
+        let deep = "1x(".repeat(20_000) + "a 1s" + &")".repeat(20_000);
+        assert_eq!(
+            error(&deep),
+            PlanError {
+                kind: PlanErrorKind::TooDeep,
+                position: 3 * MAX_DEPTH + 2
+            }
+        );
+        let fine = "1x(".repeat(MAX_DEPTH) + "a 1s" + &")".repeat(MAX_DEPTH);
+        assert_eq!(Plan::parse(&fine).unwrap().schedule().len(), 1);
+    }
+
+    #[test]
+    fn runs_every_phase_without_sleeping() {
+        let clock = ManualClock::default();
+        let timer = Timer::new(&Plan::parse("3x(work 3s, rest 2s), cooldown 4s").unwrap());
+        let mut ticks = 0;
+
+        let summary = timer.run(
+            &clock,
+            || None,
+            |event| {
+                if let Event::Tick { .. } = event {
+                    ticks += 1;
+                }
+            },
+        );
+
+        assert!(summary.completed);
+        assert_eq!(clock.now(), seconds(19));
+        assert_eq!(ticks, 19);
+        assert_eq!(
+            summary.phases[0],
+            PhaseTotal {
+                name: String::from("work"),
+                runs: 3,
+                elapsed: seconds(9),
+                paused: Duration::ZERO
+            }
+        );
+        assert_eq!(summary.phases[1].elapsed, seconds(6));
+        assert_eq!(summary.elapsed(), seconds(19));
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let clock = ManualClock::default();
+        let timer = Timer::new(&Plan::parse("work 5s, rest 2s").unwrap());
+
+        // pause one second in and resume three seconds later, pressing enter both times
+        let mut script = [(1, Command::Toggle), (4, Command::Toggle)]
+            .into_iter()
+            .peekable();
+        let summary = timer.run(
+            &clock,
+            || {
+                script
+                    .next_if(|(at, _)| clock.now() >= seconds(*at))
+                    .map(|(_, command)| command)
+            },
+            |_| {},
+        );
+
+        assert!(summary.completed);
+        assert_eq!(summary.phases[0].elapsed, seconds(5));
+        assert_eq!(summary.phases[0].paused, seconds(3));
+        assert_eq!(summary.paused(), seconds(3));
+        assert_eq!(clock.now(), seconds(10));
+    }
+
+    #[test]
+    fn quitting_stops_early() {
+        let clock = ManualClock::default();
+        let timer = Timer::new(&Plan::parse("work 5s, rest 5s").unwrap());
+        let mut events = Vec::new();
+
+        let summary = timer.run(
+            &clock,
+            || (clock.now() == seconds(2)).then_some(Command::Quit),
+            |event| events.push(format!("{event:?}")),
+        );
+
+        assert!(!summary.completed);
+        assert_eq!(summary.phases.len(), 1);
+        assert_eq!(summary.phases[0].elapsed, seconds(2));
+        assert!(events.last().unwrap().starts_with("Ended"));
+        assert!(summary.to_string().ends_with("stopped before the end"));
+    }
+
+    #[test]
+    fn commands() {
+        assert_eq!("".parse(), Ok(Command::Toggle));
+        assert_eq!(" P ".parse(), Ok(Command::Pause));
+        assert_eq!("resume".parse(), Ok(Command::Resume));
+        assert_eq!("q".parse(), Ok(Command::Quit));
+        assert!("later".parse::<Command>().is_err());
+    }
+}